@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::ocr;
+
+/// The answer to a problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    /// An integer answer.
+    Int(i64),
+    /// An integer answer too big for an `i64`.
+    BigInt(i128),
+    /// Any other answer printed as text.
+    Text(String),
+    /// Letters drawn on a screen, e.g. day 10's CRT.
+    Bitmap(Bitmap),
+}
+
+impl Answer {
+    /// The name of the variant used in reports: `int`, `bigint`, `text` or
+    /// `bitmap`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Int(_) => "int",
+            Self::BigInt(_) => "bigint",
+            Self::Text(_) => "text",
+            Self::Bitmap(_) => "bitmap",
+        }
+    }
+
+    /// Rebuilds an answer from its kind and text form.
+    pub fn from_kind(kind: &str, text: &str) -> Result<Self> {
+        match kind {
+            "int" => Ok(Self::Int(text.trim().parse()?)),
+            "bigint" => Ok(Self::BigInt(text.trim().parse()?)),
+            "text" => Ok(Self::Text(text.to_owned())),
+            "bitmap" => Ok(Self::Bitmap(text.parse()?)),
+            _ => Err(anyhow!("unknown answer kind: {}", kind)),
+        }
+    }
+
+    /// Checks the answer against an expected answer in its text form.
+    /// Integers are compared by value and bitmaps either line by line,
+    /// ignoring trailing whitespace, or by their decoded letters.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = expected.trim_end();
+        match self {
+            Self::Int(x) => expected.trim().parse::<i64>().is_ok_and(|e| e == *x),
+            Self::BigInt(x) => expected.trim().parse::<i128>().is_ok_and(|e| e == *x),
+            Self::Text(x) => x.trim_end() == expected,
+            Self::Bitmap(b) => {
+                b.to_string()
+                    .lines()
+                    .map(|x| x.trim_end())
+                    .eq(expected.lines().map(|x| x.trim_end()))
+                    || ocr::decode(b).is_ok_and(|x| x == expected.trim())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(x) => write!(f, "{}", x),
+            Self::BigInt(x) => write!(f, "{}", x),
+            Self::Text(x) => write!(f, "{}", x),
+            Self::Bitmap(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        let s = self.to_string();
+        s == *other
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty), *) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Self {
+                    match i64::try_from(x) {
+                        Ok(x) => Self::Int(x),
+                        Err(_) => Self::BigInt(x as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, u32, i64, u64, isize, usize);
+
+impl From<String> for Answer {
+    fn from(x: String) -> Self {
+        Self::Text(x)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(x: &str) -> Self {
+        Self::Text(x.to_owned())
+    }
+}
+
+impl From<Bitmap> for Answer {
+    fn from(x: Bitmap) -> Self {
+        Self::Bitmap(x)
+    }
+}
+
+/// A monochrome image such as the day 10 CRT output. Displayed as lines of
+/// `#` (on) and `.` (off).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Creates a bitmap with every pixel off.
+    pub fn new(width: usize, height: usize) -> Self {
+        Bitmap {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    /// Returns the width and height.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns whether the pixel is on or `None` if it is out of range.
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.pixels[y * self.width + x])
+    }
+
+    /// Turns a pixel on or off. Panics if it is out of range.
+    pub fn set(&mut self, x: usize, y: usize, v: bool) {
+        assert!(x < self.width && y < self.height, "pixel out of range");
+        self.pixels[y * self.width + x] = v;
+    }
+
+    /// Draws the bitmap one row per line.
+    pub fn render(&self, on: char, off: char) -> String {
+        let mut ret = String::with_capacity((self.width + 1) * self.height);
+        for (i, row) in self.pixels.chunks(self.width.max(1)).enumerate() {
+            if i != 0 {
+                ret.push('\n');
+            }
+            ret.extend(row.iter().map(|&x| if x { on } else { off }));
+        }
+
+        ret
+    }
+}
+
+impl std::str::FromStr for Bitmap {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let rows: Vec<&str> = s.lines().collect();
+        let width = rows.first().map_or(0, |x| x.len());
+
+        let mut ret = Bitmap::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(anyhow!("bitmap row {} has the wrong width", y + 1));
+            }
+
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '#' => ret.set(x, y, true),
+                    '.' => (),
+                    _ => return Err(anyhow!("invalid bitmap pixel: {:?}", c)),
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+impl fmt::Display for Bitmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render('#', '.'))
+    }
+}
+
+/// Returns the known answers for the checked in puzzle inputs of a year.
+pub fn default_answers(year: usize) -> Option<&'static str> {
+    match year {
+        2022 => Some(include_str!("../puzzle-answers/2022.txt")),
+        _ => None,
+    }
+}
+
+/// Expected answers keyed by (day, problem).
+///
+/// The file format is a sequence of sections. Each section starts with a
+/// header line of the form `[DD-P]` and the answer is every line until the
+/// next header, ignoring trailing blank lines. This allows multi-line answers
+/// such as the day 10 CRT output.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(usize, usize), String>,
+}
+
+impl Answers {
+    /// Reads an answers file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path).context("failed to read answers file")?;
+        Self::parse(&data)
+    }
+
+    /// Returns the checked in answers of a year, none if there are no
+    /// [`default_answers`] for it.
+    pub fn for_year(year: usize) -> Result<Self> {
+        Self::parse(default_answers(year).unwrap_or(""))
+    }
+
+    /// Parses an answers file.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut answers = HashMap::new();
+        let mut cur: Option<((usize, usize), Vec<&str>)> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            if let Some(key) = parse_header(line) {
+                if let Some((k, v)) = cur.take() {
+                    insert(&mut answers, k, v)?;
+                }
+                cur = Some((key, Vec::new()));
+                continue;
+            }
+
+            match cur.as_mut() {
+                Some((_, v)) => v.push(line),
+                None if line.trim().is_empty() => (),
+                None => return Err(anyhow!("line {}: answer before first header", i + 1)),
+            }
+        }
+
+        if let Some((k, v)) = cur {
+            insert(&mut answers, k, v)?;
+        }
+
+        Ok(Answers { answers })
+    }
+
+    /// Returns the expected answer for a problem.
+    pub fn get(&self, day: usize, problem: usize) -> Option<&str> {
+        self.answers.get(&(day, problem)).map(|x| x.as_str())
+    }
+
+    /// Compares an answer with the expected answer for a problem, see
+    /// [`Answer::matches`].
+    pub fn check(&self, day: usize, problem: usize, answer: &Answer) -> Verdict {
+        match self.get(day, problem) {
+            Some(expected) if answer.matches(expected) => Verdict::Pass,
+            Some(expected) => Verdict::Fail(diff(expected, &answer.to_string())),
+            None => Verdict::Missing,
+        }
+    }
+}
+
+/// The outcome of checking a problem against its expected answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer is correct.
+    Pass,
+    /// The answer is wrong. Holds a [`diff`] of the expected and actual
+    /// answers.
+    Fail(String),
+    /// There is no expected answer to compare with.
+    Missing,
+    /// Solving failed with this error.
+    Error(String),
+}
+
+impl Verdict {
+    /// Returns whether the problem failed, with a wrong answer or an error.
+    pub fn failed(&self) -> bool {
+        matches!(self, Verdict::Fail(_) | Verdict::Error(_))
+    }
+}
+
+/// Line based diff of the expected and actual answers for display. Lines
+/// that match are indented, expected lines are prefixed with `- ` and actual
+/// lines with `+ `.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let mut ret = Vec::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => ret.push(format!("      {}", e)),
+            (e, a) => {
+                if let Some(e) = e {
+                    ret.push(format!("    - {}", e));
+                }
+                if let Some(a) = a {
+                    ret.push(format!("    + {}", a));
+                }
+            }
+        }
+    }
+
+    ret.join("\n")
+}
+
+fn insert(
+    answers: &mut HashMap<(usize, usize), String>,
+    key: (usize, usize),
+    mut lines: Vec<&str>,
+) -> Result<()> {
+    while lines.last().is_some_and(|x| x.trim().is_empty()) {
+        lines.pop();
+    }
+
+    if answers.insert(key, lines.join("\n")).is_some() {
+        return Err(anyhow!("duplicate answer for {}-{}", key.0, key.1));
+    }
+
+    Ok(())
+}
+
+// parses "[10-2]" to (10, 2)
+fn parse_header(line: &str) -> Option<(usize, usize)> {
+    let (day, problem) = line
+        .trim_end()
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .split_once('-')?;
+
+    Some((day.parse().ok()?, problem.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_matches_test() {
+        assert!(Answer::from(24000usize).matches("24000\n"));
+        assert!(!Answer::from(24000usize).matches("2400"));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert!(Answer::from(u64::MAX).matches("18446744073709551615"));
+        assert!(Answer::from("2=-1=0").matches("2=-1=0"));
+
+        let mut b = Bitmap::new(3, 2);
+        b.set(0, 0, true);
+        b.set(2, 1, true);
+        assert_eq!(Answer::from(b.clone()), "#..\n..#");
+        assert!(Answer::from(b.clone()).matches("#..\n..#\n"));
+        assert_eq!(b.render('X', ' '), "X  \n  X");
+
+        let mut l = Bitmap::new(4, 6);
+        (0..6).for_each(|y| l.set(0, y, true));
+        (0..4).for_each(|x| l.set(x, 5, true));
+        assert!(Answer::from(l).matches("L"));
+    }
+
+    #[test]
+    fn answer_from_kind_test() {
+        for ans in [
+            Answer::from(7usize),
+            Answer::from(u64::MAX),
+            Answer::from("a\nb"),
+            Answer::Bitmap("#.\n.#".parse().unwrap()),
+        ] {
+            assert_eq!(
+                Answer::from_kind(ans.kind(), &ans.to_string()).unwrap(),
+                ans
+            );
+        }
+
+        assert!(Answer::from_kind("bitmap", "#.\n#").is_err());
+    }
+
+    #[test]
+    fn parse_test() {
+        let input = "[01-1]\n24000\n\n[10-2]\n##..\n..##\n\n\n[25-2]\n[Start The Blender]\n";
+        let answers = Answers::parse(input).unwrap();
+
+        assert_eq!(answers.get(1, 1), Some("24000"));
+        assert_eq!(answers.get(10, 2), Some("##..\n..##"));
+        assert_eq!(answers.get(25, 2), Some("[Start The Blender]"));
+        assert_eq!(answers.get(1, 2), None);
+    }
+
+    #[test]
+    fn check_test() {
+        let answers = Answers::parse("[01-1]\n24000\n[10-2]\n#.\n.#\n").unwrap();
+        assert_eq!(answers.check(1, 1, &24000usize.into()), Verdict::Pass);
+        assert_eq!(answers.check(1, 2, &1usize.into()), Verdict::Missing);
+        assert_eq!(
+            answers.check(10, 2, &"#.\n##".into()),
+            Verdict::Fail("      #.\n    - .#\n    + ##".to_owned())
+        );
+        assert!(answers.check(1, 1, &1usize.into()).failed());
+    }
+
+    #[test]
+    fn default_answers_test() {
+        let answers = Answers::parse(default_answers(2022).unwrap()).unwrap();
+        for day in 1..=25 {
+            for problem in [1, 2] {
+                assert!(answers.get(day, problem).is_some());
+            }
+        }
+    }
+}
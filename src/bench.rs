@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::inputs::InputSource;
+use crate::runner::{run_problem, DayOptions};
+
+/// Summary statistics over repeated runs of a single problem.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    /// The fastest run.
+    pub min: Duration,
+    /// The median run, used to detect regressions.
+    pub median: Duration,
+    /// The mean of all runs.
+    pub mean: Duration,
+    /// The 95th percentile by nearest rank.
+    pub p95: Duration,
+    /// The population standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics or returns `None` without samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let total: Duration = sorted.iter().sum();
+        let mean = total / sorted.len() as u32;
+
+        let variance = sorted
+            .iter()
+            .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+
+        Some(Stats {
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            mean,
+            p95: percentile(&sorted, 95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+// Nearest rank percentile. `sorted` must be non-empty and sorted.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+/// Saved benchmark results keyed by (year, day, problem).
+///
+/// Stored as one line per problem: `YYYY-DD-P min median mean p95 stddev`
+/// with every duration in nanoseconds.
+#[derive(Clone, Debug, Default)]
+pub struct Baseline {
+    /// The statistics of each problem.
+    pub results: HashMap<(usize, usize, usize), Stats>,
+}
+
+impl Baseline {
+    /// Reads a baseline file.
+    pub fn load(path: &str) -> Result<Self> {
+        let data = std::fs::read_to_string(path).context("failed to read baseline file")?;
+        Self::parse(&data)
+    }
+
+    /// Writes the baseline to a file.
+    pub fn save(&self, path: &str) -> Result<()> {
+        std::fs::write(path, self.to_string()).context("failed to write baseline file")
+    }
+
+    /// Parses the contents of a baseline file.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut results = HashMap::new();
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, stats) = parse_line(line).ok_or_else(|| {
+                anyhow!(
+                    "line {}: malformed baseline, expected YYYY-DD-P and five durations",
+                    i + 1
+                )
+            })?;
+            results.insert(key, stats);
+        }
+
+        Ok(Baseline { results })
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut keys: Vec<_> = self.results.keys().collect();
+        keys.sort();
+
+        for &(year, day, problem) in keys {
+            let s = &self.results[&(year, day, problem)];
+            writeln!(
+                f,
+                "{}-{:02}-{} {} {} {} {} {}",
+                year,
+                day,
+                problem,
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.p95.as_nanos(),
+                s.stddev.as_nanos()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<((usize, usize, usize), Stats)> {
+    let mut fields = line.split_whitespace();
+    let mut key = fields.next()?.split('-').map(|x| x.parse().ok());
+    let key = (key.next()??, key.next()??, key.next()??);
+
+    let mut next =
+        || -> Option<Duration> { Some(Duration::from_nanos(fields.next()?.parse().ok()?)) };
+    let stats = Stats {
+        min: next()?,
+        median: next()?,
+        mean: next()?,
+        p95: next()?,
+        stddev: next()?,
+    };
+
+    Some((key, stats))
+}
+
+/// How [`run`] benchmarks each problem.
+#[derive(Clone, Debug)]
+pub struct Settings {
+    /// Timed runs per problem.
+    pub iterations: usize,
+    /// Untimed runs per problem before the timed ones.
+    pub warmup: usize,
+    /// Results to compare with for regressions.
+    pub baseline: Option<Baseline>,
+    /// Percent slowdown of the median before flagging a regression.
+    pub threshold: f64,
+}
+
+/// The benchmark of a single problem.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// The year of the puzzle.
+    pub year: usize,
+    /// The day of the puzzle.
+    pub day: usize,
+    /// The problem number.
+    pub problem: usize,
+    /// Statistics of parsing the input.
+    pub parse: Stats,
+    /// Statistics of solving the problem.
+    pub solve: Stats,
+    /// The percent slowdown from the baseline if it is a regression, see
+    /// [`regression`].
+    pub regression: Option<f64>,
+}
+
+/// Benchmarks `problems`, given as (day, problem), of `year` one after the
+/// other. `progress` is called with each measurement as soon as it is taken.
+/// Stops at the first problem that fails.
+pub fn run(
+    year: usize,
+    problems: &[(usize, usize)],
+    settings: &Settings,
+    source: &InputSource,
+    options: &DayOptions,
+    mut progress: impl FnMut(&Measurement),
+) -> Result<Vec<Measurement>> {
+    if settings.iterations == 0 {
+        bail!("iterations must be at least 1");
+    }
+
+    let mut ret = Vec::new();
+    for &(day, problem) in problems {
+        for _ in 0..settings.warmup {
+            run_problem(year, day, problem, source, options)?;
+        }
+
+        let timings = (0..settings.iterations)
+            .map(|_| run_problem(year, day, problem, source, options).map(|x| x.1))
+            .collect::<Result<Vec<_>>>()?;
+        let solve_samples: Vec<_> = timings.iter().map(|x| x.solve).collect();
+        let parse_samples: Vec<_> = timings.iter().map(|x| x.parse).collect();
+        let solve = Stats::from_samples(&solve_samples).unwrap();
+
+        let measurement = Measurement {
+            year,
+            day,
+            problem,
+            parse: Stats::from_samples(&parse_samples).unwrap(),
+            solve,
+            regression: settings
+                .baseline
+                .as_ref()
+                .and_then(|b| b.results.get(&(year, day, problem)))
+                .and_then(|old| regression(old, &solve, settings.threshold)),
+        };
+        progress(&measurement);
+        ret.push(measurement);
+    }
+
+    Ok(ret)
+}
+
+impl FromIterator<Measurement> for Baseline {
+    /// Collects the solve statistics of each measurement.
+    fn from_iter<I: IntoIterator<Item = Measurement>>(iter: I) -> Self {
+        Baseline {
+            results: iter
+                .into_iter()
+                .map(|m| ((m.year, m.day, m.problem), m.solve))
+                .collect(),
+        }
+    }
+}
+
+/// Returns the relative change of the median from `old` to `new` if it is
+/// slower by more than `threshold` percent.
+pub fn regression(old: &Stats, new: &Stats, threshold: f64) -> Option<f64> {
+    let old = old.median.as_secs_f64();
+    let new = new.median.as_secs_f64();
+    if old == 0.0 {
+        return None;
+    }
+
+    let change = (new - old) / old * 100.0;
+    (change > threshold).then_some(change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats_test() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3].into_iter().map(ms).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(3));
+        assert_eq!(stats.p95, ms(5));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+    }
+
+    #[test]
+    fn baseline_round_trip_test() {
+        let stats = Stats::from_samples(&[ms(1), ms(2), ms(10)]).unwrap();
+        let mut baseline = Baseline::default();
+        baseline.results.insert((2022, 15, 2), stats);
+
+        let parsed = Baseline::parse(&baseline.to_string()).unwrap();
+        assert_eq!(parsed.results[&(2022, 15, 2)], stats);
+        assert!(baseline.to_string().starts_with("2022-15-2 "));
+
+        // Baselines from before the year was recorded are rejected.
+        assert!(Baseline::parse("15-2 1 2 3 4 5").is_err());
+    }
+
+    #[test]
+    fn regression_test() {
+        let old = Stats::from_samples(&[ms(100)]).unwrap();
+        let new = Stats::from_samples(&[ms(115)]).unwrap();
+
+        assert!(regression(&old, &new, 20.0).is_none());
+        assert!(regression(&old, &new, 10.0).is_some());
+        assert!(regression(&new, &old, 10.0).is_none());
+    }
+
+    #[test]
+    fn run_test() {
+        let settings = Settings {
+            iterations: 2,
+            warmup: 0,
+            baseline: None,
+            threshold: 10.0,
+        };
+        let source = InputSource::Example("example".to_owned());
+        let options = DayOptions::default();
+        let mut seen = 0;
+        let results = run(
+            2022,
+            &[(1, 1), (1, 2)],
+            &settings,
+            &source,
+            &options,
+            |_| seen += 1,
+        )
+        .unwrap();
+        assert_eq!(seen, 2);
+        let baseline: Baseline = results.into_iter().collect();
+        assert!(baseline.results.contains_key(&(2022, 1, 2)));
+
+        let settings = Settings {
+            iterations: 0,
+            ..settings
+        };
+        assert!(run(2022, &[(1, 1)], &settings, &source, &options, |_| ()).is_err());
+    }
+}
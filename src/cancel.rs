@@ -0,0 +1,109 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+
+/// A flag long running searches poll so the runner can stop them.
+///
+/// Solutions get the token for the problem being solved with [`current`].
+/// Work handed to other threads (e.g. with rayon) has to take the token along
+/// since it is only set on the thread calling the solution.
+#[derive(Clone, Debug, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    /// Asks whoever holds the token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether [`Token::cancel`] has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error once the token has been cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            bail!("cancelled");
+        }
+
+        Ok(())
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Token> = RefCell::new(Token::default());
+}
+
+/// The token of the problem running on this thread. Never cancelled outside
+/// of [`with_token`].
+pub fn current() -> Token {
+    CURRENT.with(|x| x.borrow().clone())
+}
+
+/// Runs `f` with `token` as the current token of this thread.
+pub fn with_token<R>(token: Token, f: impl FnOnce() -> R) -> R {
+    let old = CURRENT.with(|x| x.replace(token));
+    let ret = f();
+    CURRENT.with(|x| x.replace(old));
+
+    ret
+}
+
+/// The error recorded for a problem that did not finish within its timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Parses a timeout such as `500ms`, `10s` or `2m`. A bare number is seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration> {
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid timeout: {}", s))?;
+
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        _ => bail!("unknown timeout unit: {}", unit),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow!("invalid timeout: {}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_token_test() {
+        let token = Token::default();
+        token.cancel();
+
+        assert!(with_token(token, || current().check().is_err()));
+        assert!(current().check().is_ok());
+    }
+
+    #[test]
+    fn parse_timeout_test() {
+        assert_eq!(parse_timeout("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_timeout("1.5").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_timeout("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_timeout("5h").is_err());
+        assert!(parse_timeout("-1s").is_err());
+    }
+}
@@ -1,26 +1,119 @@
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+use anyhow::{bail, Result};
+
+/// A rectangular grid of cells stored row by row in one buffer.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
-    pub cells: Vec<Vec<T>>,
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
 impl<T> Grid<T> {
-    pub fn new(data: Vec<Vec<T>>) -> Self {
-        Grid { cells: data }
+    /// Creates a `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from its rows. Fails if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |x| x.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                bail!(
+                    "row {} has {} cells but the first row has {}",
+                    y,
+                    row.len(),
+                    width
+                );
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
     }
 
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the width and height of the grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns the cell at `p` or `None` if it is outside the grid.
     pub fn get(&self, p: Point) -> Option<&T> {
-        self.cells.get(p.y)?.get(p.x)
+        let i = self.index(p)?;
+        self.cells.get(i)
     }
 
+    /// Mutable version of [`Grid::get`].
     pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
-        self.cells.get_mut(p.y)?.get_mut(p.x)
+        let i = self.index(p)?;
+        self.cells.get_mut(i)
+    }
+
+    /// Returns row `y` or `None` if it is outside the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Mutable version of [`Grid::row`].
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Iterates over the rows from `y = 0`.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
     }
 
+    /// Iterates over the cells of column `x` from `y = 0`. Empty if `x` is
+    /// outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Iterates over every point in the grid row by row.
     pub fn iter_points(&self) -> impl Iterator<Item = Point> {
         let (x_len, y_len) = self.size();
         (0..y_len).flat_map(move |y| (0..x_len).map(move |x| Point::new(x, y)))
     }
 
+    /// Iterates over every point and its cell row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.iter_points().zip(&self.cells)
+    }
+
+    /// Iterates over the cells from `start` in direction `d` until the edge
+    /// of the grid. `start` is included.
     pub fn iter_line(&self, start: Point, d: Direction) -> impl Iterator<Item = (Point, &T)> {
         LineIterator {
             g: self,
@@ -29,14 +122,42 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn size(&self) -> (usize, usize) {
-        (self.cells[0].len(), self.cells.len())
+    // The position of `p` in the buffer, `None` if `p` is outside the grid.
+    fn index(&self, p: Point) -> Option<usize> {
+        if p.x >= self.width || p.y >= self.height {
+            return None;
+        }
+        Some(p.y * self.width + p.x)
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self> {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// Returns the cell at `p`, panicking if it is outside the grid.
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(x) => x,
+            None => panic!("{:?} is outside the {}x{} grid", p, self.width, self.height),
+        }
     }
 }
 
-impl<T> From<Vec<Vec<T>>> for Grid<T> {
-    fn from(cells: Vec<Vec<T>>) -> Self {
-        Self::new(cells)
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = self.size();
+        match self.get_mut(p) {
+            Some(x) => x,
+            None => panic!("{:?} is outside the {}x{} grid", p, width, height),
+        }
     }
 }
 
@@ -58,17 +179,196 @@ impl<'a, T> Iterator for LineIterator<'a, T> {
     }
 }
 
+/// A grid with signed coordinates that grows in whichever direction cells
+/// are written, for simulations whose extent isn't known up front. Cells
+/// never written read as the default value.
+#[derive(Clone, Debug)]
+pub struct ExpandingGrid<T> {
+    cells: Grid<T>,
+    // The position of the first stored cell.
+    origin: Vector,
+    // The smallest and largest corners of the cells written so far.
+    bounds: Option<(Vector, Vector)>,
+    default: T,
+}
+
+impl<T: Clone> ExpandingGrid<T> {
+    /// Creates an empty grid where every cell reads as `default`.
+    pub fn new(default: T) -> Self {
+        ExpandingGrid {
+            cells: Grid::new(0, 0, default.clone()),
+            origin: Vector::default(),
+            bounds: None,
+            default,
+        }
+    }
+
+    /// Returns the cell at `p`.
+    pub fn get(&self, p: Vector) -> &T {
+        self.point(p)
+            .and_then(|q| self.cells.get(q))
+            .unwrap_or(&self.default)
+    }
+
+    /// Returns the cell at `p` for writing, growing the grid to include it.
+    pub fn get_mut_or_expand(&mut self, p: Vector) -> &mut T {
+        self.reserve(p);
+        self.bounds = Some(include(self.bounds, p));
+
+        let q = self.point(p).expect("reserved point is in the grid");
+        &mut self.cells[q]
+    }
+
+    /// Sets the cell at `p`, growing the grid to include it.
+    pub fn set(&mut self, p: Vector, value: T) {
+        *self.get_mut_or_expand(p) = value;
+    }
+
+    /// Returns the smallest and largest corners of the rectangle holding
+    /// every cell written, or `None` if nothing has been written.
+    pub fn bounds(&self) -> Option<(Vector, Vector)> {
+        self.bounds
+    }
+
+    /// Returns the number of cells within the [`bounds`](Self::bounds).
+    pub fn area(&self) -> usize {
+        match self.bounds {
+            None => 0,
+            Some((min, max)) => ((max.x - min.x + 1) * (max.y - min.y + 1)) as usize,
+        }
+    }
+
+    /// Iterates over every position within the [`bounds`](Self::bounds) and
+    /// its cell row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        let (min, max) = self
+            .bounds
+            .unwrap_or((Vector::new(0, 0), Vector::new(-1, -1)));
+        (min.y..=max.y).flat_map(move |y| {
+            (min.x..=max.x).map(move |x| {
+                let p = Vector::new(x, y);
+                (p, self.get(p))
+            })
+        })
+    }
+
+    /// Renders the cells within the [`bounds`](Self::bounds) one row per
+    /// line, smallest `y` first, with `cell` giving the character for each.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        let mut ret = String::new();
+        for y in min.y..=max.y {
+            if y != min.y {
+                ret.push('\n');
+            }
+            ret.extend((min.x..=max.x).map(|x| cell(self.get(Vector::new(x, y)))));
+        }
+        ret
+    }
+
+    // The position of `p` in `cells`, which may be past its edge.
+    fn point(&self, p: Vector) -> Option<Point> {
+        Point::try_from(p - self.origin).ok()
+    }
+
+    // Makes room for `p`. The storage at least doubles along each axis it
+    // grows on, so growing one cell at a time takes amortised constant time.
+    fn reserve(&mut self, p: Vector) {
+        if self.point(p).is_some_and(|q| self.cells.get(q).is_some()) {
+            return;
+        }
+
+        let (width, height) = self.cells.size();
+        let (mut min, mut max) = match (width, height) {
+            (0, _) | (_, 0) => (p, p),
+            _ => (
+                self.origin,
+                self.origin + Vector::new(width as isize - 1, height as isize - 1),
+            ),
+        };
+        let (width, height) = (width as isize, height as isize);
+        if p.x < min.x {
+            min.x = p.x.min(max.x + 1 - 2 * width);
+        } else if p.x > max.x {
+            max.x = p.x.max(min.x - 1 + 2 * width);
+        }
+        if p.y < min.y {
+            min.y = p.y.min(max.y + 1 - 2 * height);
+        } else if p.y > max.y {
+            max.y = p.y.max(min.y - 1 + 2 * height);
+        }
+
+        let mut cells = Grid::new(
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            self.default.clone(),
+        );
+        let offset = self.origin - min;
+        for (y, row) in self.cells.rows().enumerate() {
+            let start = offset.x as usize;
+            let new_row = cells
+                .row_mut(y + offset.y as usize)
+                .expect("new storage covers the old");
+            new_row[start..start + row.len()].clone_from_slice(row);
+        }
+
+        self.cells = cells;
+        self.origin = min;
+    }
+}
+
+impl<T: Clone + PartialEq> ExpandingGrid<T> {
+    /// Shrinks the [`bounds`](Self::bounds) to the cells that differ from the
+    /// default value.
+    pub fn trim(&mut self) {
+        let mut bounds: Option<(Vector, Vector)> = None;
+        for (p, v) in self.iter() {
+            if *v == self.default {
+                continue;
+            }
+            bounds = Some(include(bounds, p));
+        }
+        self.bounds = bounds;
+    }
+}
+
+impl<T: Clone + Default> Default for ExpandingGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+// Grows the rectangle `bounds` to include `p`.
+fn include(bounds: Option<(Vector, Vector)>, p: Vector) -> (Vector, Vector) {
+    match bounds {
+        None => (p, p),
+        Some((min, max)) => (
+            Vector::new(min.x.min(p.x), min.y.min(p.y)),
+            Vector::new(max.x.max(p.x), max.y.max(p.y)),
+        ),
+    }
+}
+
+/// A position in a [`Grid`].
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Point {
+    /// The column.
     pub x: usize,
+    /// The row.
     pub y: usize,
 }
 
 impl Point {
+    /// Creates a point.
     pub fn new(x: usize, y: usize) -> Self {
         Point { x, y }
     }
 
+    /// Returns the neighbouring point in direction `d` or `None` if it would
+    /// be negative or overflow.
     pub fn next(&self, d: Direction) -> Option<Point> {
         let p = match d {
             Direction::Up => Point::new(self.x, self.y.checked_add(1)?),
@@ -80,25 +380,206 @@ impl Point {
         Some(p)
     }
 
+    /// Iterates over the four orthogonal neighbours.
     pub fn iter_adjacent(&self) -> impl Iterator<Item = Point> {
         let p = *self;
         Direction::iter().filter_map(move |d| p.next(d))
     }
 
+    /// Returns the neighbouring point in direction `d`, which may be
+    /// diagonal, or `None` if it would be negative or overflow.
+    pub fn next8(&self, d: Direction8) -> Option<Point> {
+        let offset = Vector::from(d);
+        Some(Point::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+
+    /// Iterates over the eight orthogonal and diagonal neighbours.
+    pub fn iter_surrounding(&self) -> impl Iterator<Item = Point> {
+        let p = *self;
+        Direction8::iter().filter_map(move |d| p.next8(d))
+    }
+
+    /// Iterates over the three neighbours on the `d` side: the one in
+    /// direction `d` and the diagonal ones either side of it.
+    pub fn iter_facing(&self, d: Direction) -> impl Iterator<Item = Point> {
+        let p = *self;
+        d.facing().into_iter().filter_map(move |d| p.next8(d))
+    }
+
+    /// Returns the Manhattan distance between two points.
     pub fn manhattan_distance(&self, other: &Self) -> usize {
         (self.x.abs_diff(other.x)) + (self.y.abs_diff(other.y))
     }
 }
 
+impl TryFrom<Vector> for Point {
+    type Error = TryFromIntError;
+
+    /// Converts a signed position to a grid position, failing if either
+    /// coordinate is negative.
+    fn try_from(v: Vector) -> Result<Self, Self::Error> {
+        Ok(Point::new(v.x.try_into()?, v.y.try_into()?))
+    }
+}
+
+/// A signed position or offset on an unbounded plane. Unlike [`Point`] it
+/// can go negative and supports arithmetic, e.g. `p + Vector::from(d) * 2`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Vector {
+    /// The column.
+    pub x: isize,
+    /// The row.
+    pub y: isize,
+}
+
+impl Vector {
+    /// Creates a vector.
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vector { x, y }
+    }
+
+    /// Returns the neighbouring position in direction `d`.
+    pub fn step(&self, d: Direction) -> Vector {
+        *self + Vector::from(d)
+    }
+
+    /// Returns the neighbouring position in direction `d`, which may be
+    /// diagonal.
+    pub fn step8(&self, d: Direction8) -> Vector {
+        *self + Vector::from(d)
+    }
+
+    /// Iterates over the eight orthogonal and diagonal neighbours.
+    pub fn iter_surrounding(&self) -> impl Iterator<Item = Vector> {
+        let p = *self;
+        Direction8::iter().map(move |d| p.step8(d))
+    }
+
+    /// Iterates over the three neighbours on the `d` side: the one in
+    /// direction `d` and the diagonal ones either side of it.
+    pub fn iter_facing(&self, d: Direction) -> impl Iterator<Item = Vector> {
+        let p = *self;
+        d.facing().into_iter().map(move |d| p.step8(d))
+    }
+
+    /// Returns the vector with each coordinate replaced by its sign, i.e. a
+    /// single step towards where this vector points.
+    pub fn signum(&self) -> Vector {
+        Vector::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Returns the Manhattan distance between two positions, the number of
+    /// orthogonal steps from one to the other.
+    pub fn manhattan_distance(&self, other: &Self) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Returns the Chebyshev distance between two positions, the number of
+    /// steps from one to the other when diagonal steps are allowed.
+    pub fn chebyshev_distance(&self, other: &Self) -> isize {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl TryFrom<Point> for Vector {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point) -> Result<Self, Self::Error> {
+        Ok(Vector::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+impl From<Direction> for Vector {
+    /// The unit vector pointing in the direction.
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => Vector::new(0, 1),
+            Direction::Down => Vector::new(0, -1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::Right => Vector::new(1, 0),
+        }
+    }
+}
+
+impl From<Direction8> for Vector {
+    /// The step to the neighbour in the direction, e.g. `(1, 1)` for
+    /// `UpRight`.
+    fn from(d: Direction8) -> Self {
+        match d {
+            Direction8::Up => Vector::new(0, 1),
+            Direction8::UpRight => Vector::new(1, 1),
+            Direction8::Right => Vector::new(1, 0),
+            Direction8::DownRight => Vector::new(1, -1),
+            Direction8::Down => Vector::new(0, -1),
+            Direction8::DownLeft => Vector::new(-1, -1),
+            Direction8::Left => Vector::new(-1, 0),
+            Direction8::UpLeft => Vector::new(-1, 1),
+        }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: isize) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four orthogonal directions. `Up` increases `y`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Direction {
+    /// Towards larger `y`.
     Up,
+    /// Towards smaller `y`.
     Down,
+    /// Towards smaller `x`.
     Left,
+    /// Towards larger `x`.
     Right,
 }
 
 impl Direction {
+    /// Iterates over every direction.
     pub fn iter() -> impl Iterator<Item = Self> {
         [
             Direction::Up,
@@ -108,4 +589,428 @@ impl Direction {
         ]
         .into_iter()
     }
+
+    /// Returns this direction and the two diagonals either side of it, the
+    /// directions of the three cells facing `self`.
+    pub fn facing(&self) -> [Direction8; 3] {
+        match self {
+            Direction::Up => [Direction8::UpLeft, Direction8::Up, Direction8::UpRight],
+            Direction::Down => [
+                Direction8::DownLeft,
+                Direction8::Down,
+                Direction8::DownRight,
+            ],
+            Direction::Left => [Direction8::UpLeft, Direction8::Left, Direction8::DownLeft],
+            Direction::Right => [
+                Direction8::UpRight,
+                Direction8::Right,
+                Direction8::DownRight,
+            ],
+        }
+    }
+}
+
+/// One of the four orthogonal directions of [`Direction`] or one of the four
+/// diagonals between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    /// Towards larger `y`.
+    Up,
+    /// Towards larger `x` and `y`.
+    UpRight,
+    /// Towards larger `x`.
+    Right,
+    /// Towards larger `x` and smaller `y`.
+    DownRight,
+    /// Towards smaller `y`.
+    Down,
+    /// Towards smaller `x` and `y`.
+    DownLeft,
+    /// Towards smaller `x`.
+    Left,
+    /// Towards smaller `x` and larger `y`.
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Iterates over every direction, clockwise from `Up`.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Direction8::Up,
+            Direction8::UpRight,
+            Direction8::Right,
+            Direction8::DownRight,
+            Direction8::Down,
+            Direction8::DownLeft,
+            Direction8::Left,
+            Direction8::UpLeft,
+        ]
+        .into_iter()
+    }
+
+    /// Returns whether the direction is one of the four diagonals.
+    pub fn is_diagonal(&self) -> bool {
+        let v = Vector::from(*self);
+        v.x != 0 && v.y != 0
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+/// A box of cells in three dimensions stored in one buffer, covering the
+/// positions from a smallest to a largest corner inclusive. The corners can
+/// be negative.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    min: Vector3,
+    size: (usize, usize, usize),
+}
+
+impl<T> Grid3<T> {
+    /// Creates a grid covering `min` to `max` inclusive with every cell set
+    /// to `value`. The grid is empty if `max` is below `min` on any axis.
+    pub fn new(min: Vector3, max: Vector3, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let len = |min: isize, max: isize| usize::try_from(max - min + 1).unwrap_or(0);
+        let size = (len(min.x, max.x), len(min.y, max.y), len(min.z, max.z));
+        Grid3 {
+            cells: vec![value; size.0 * size.1 * size.2],
+            min,
+            size,
+        }
+    }
+
+    /// Returns the smallest corner of the grid.
+    pub fn min(&self) -> Vector3 {
+        self.min
+    }
+
+    /// Returns the number of cells along x, y and z.
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    /// Returns the cell at `p` or `None` if it is outside the grid.
+    pub fn get(&self, p: Vector3) -> Option<&T> {
+        let i = self.index(p)?;
+        self.cells.get(i)
+    }
+
+    /// Returns the cell at `p` or `None` if it is outside the grid.
+    pub fn get_mut(&mut self, p: Vector3) -> Option<&mut T> {
+        let i = self.index(p)?;
+        self.cells.get_mut(i)
+    }
+
+    // The position of `p` in the buffer, `None` if `p` is outside the grid.
+    fn index(&self, p: Vector3) -> Option<usize> {
+        let offset = p - self.min;
+        let x = usize::try_from(offset.x).ok()?;
+        let y = usize::try_from(offset.y).ok()?;
+        let z = usize::try_from(offset.z).ok()?;
+        let (x_len, y_len, z_len) = self.size;
+        if x >= x_len || y >= y_len || z >= z_len {
+            return None;
+        }
+        Some((z * y_len + y) * x_len + x)
+    }
+}
+
+impl<T> Index<Vector3> for Grid3<T> {
+    type Output = T;
+
+    /// Returns the cell at `p`, panicking if it is outside the grid.
+    fn index(&self, p: Vector3) -> &T {
+        match self.get(p) {
+            Some(x) => x,
+            None => panic!("{:?} is outside the {:?} grid", p, self.size),
+        }
+    }
+}
+
+impl<T> IndexMut<Vector3> for Grid3<T> {
+    fn index_mut(&mut self, p: Vector3) -> &mut T {
+        let size = self.size;
+        match self.get_mut(p) {
+            Some(x) => x,
+            None => panic!("{:?} is outside the {:?} grid", p, size),
+        }
+    }
+}
+
+/// A signed position or offset in three dimensions, the 3D counterpart of
+/// [`Vector`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Vector3 {
+    /// The x coordinate.
+    pub x: isize,
+    /// The y coordinate.
+    pub y: isize,
+    /// The z coordinate.
+    pub z: isize,
+}
+
+impl Vector3 {
+    /// Creates a vector.
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Vector3 { x, y, z }
+    }
+
+    /// Iterates over the six neighbours sharing a face with this position.
+    pub fn iter_adjacent(&self) -> impl Iterator<Item = Vector3> {
+        let p = *self;
+        [
+            Vector3::new(1, 0, 0),
+            Vector3::new(-1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, -1, 0),
+            Vector3::new(0, 0, 1),
+            Vector3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |d| p + d)
+    }
+
+    /// Iterates over the 26 neighbours sharing a face, edge or corner with
+    /// this position.
+    pub fn iter_surrounding(&self) -> impl Iterator<Item = Vector3> {
+        let p = *self;
+        (-1..=1)
+            .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| Vector3::new(x, y, z))))
+            .filter(|&d| d != Vector3::default())
+            .map(move |d| p + d)
+    }
+
+    /// Returns the Manhattan distance between two positions.
+    pub fn manhattan_distance(&self, other: &Self) -> isize {
+        let d = *self - *other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    /// Returns the smallest and largest corners of the box holding every
+    /// point, or `None` if there are none.
+    pub fn bounds(points: impl IntoIterator<Item = Vector3>) -> Option<(Vector3, Vector3)> {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((
+                Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )),
+        })
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Vector3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, other: Vector3) -> Vector3 {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Vector3) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Vector3 {
+    type Output = Vector3;
+
+    fn mul(self, n: isize) -> Vector3 {
+        Vector3::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Vector3;
+
+    fn neg(self) -> Vector3 {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_test() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+
+        grid[Point::new(0, 1)] = 7;
+        assert_eq!(grid.row(1), Some(&[7, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn grid_from_rows_test() {
+        let err = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(err.to_string(), "row 1 has 1 cells but the first row has 2");
+
+        let empty = Grid::<u8>::from_rows(Vec::new()).unwrap();
+        assert_eq!(empty.size(), (0, 0));
+        assert_eq!(empty.iter_points().count(), 0);
+        assert_eq!(empty.column(0).count(), 0);
+    }
+
+    #[test]
+    fn expanding_grid_test() {
+        let mut grid = ExpandingGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|&c| c), "");
+
+        grid.set(Vector::new(0, 0), '#');
+        grid.set(Vector::new(-3, 2), '#');
+        grid.set(Vector::new(2, -1), '#');
+        *grid.get_mut_or_expand(Vector::new(100, 0)) = '.';
+        assert_eq!(*grid.get(Vector::new(-3, 2)), '#');
+        assert_eq!(*grid.get(Vector::new(-300, 2)), '.');
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new(-3, -1), Vector::new(100, 2)))
+        );
+
+        grid.trim();
+        assert_eq!(
+            grid.bounds(),
+            Some((Vector::new(-3, -1), Vector::new(2, 2)))
+        );
+        assert_eq!(grid.area(), 24);
+        assert_eq!(grid.iter().filter(|(_, &c)| c == '#').count(), 3);
+        assert_eq!(grid.render(|&c| c), ".....#\n...#..\n......\n#.....");
+    }
+
+    #[test]
+    fn vector_test() {
+        let a = Vector::new(1, -2);
+        let b = Vector::new(-3, 4);
+        assert_eq!(a + b, Vector::new(-2, 2));
+        assert_eq!(a - b, Vector::new(4, -6));
+        assert_eq!(b * 2, Vector::new(-6, 8));
+        assert_eq!(-a, Vector::new(-1, 2));
+        assert_eq!((b - a).signum(), Vector::new(-1, 1));
+        assert_eq!(a.step(Direction::Up), Vector::new(1, -1));
+
+        assert_eq!(a.manhattan_distance(&b), 10);
+        assert_eq!(a.chebyshev_distance(&b), 6);
+    }
+
+    #[test]
+    fn direction8_test() {
+        assert_eq!(Direction8::iter().count(), 8);
+        assert_eq!(Direction8::iter().filter(|d| d.is_diagonal()).count(), 4);
+        for d in Direction::iter() {
+            assert_eq!(Vector::from(Direction8::from(d)), Vector::from(d));
+        }
+
+        let p = Vector::new(0, 0);
+        let mut around: Vec<_> = p.iter_surrounding().collect();
+        around.sort();
+        let mut expected: Vec<_> = (-1..=1)
+            .flat_map(|x| (-1..=1).map(move |y| Vector::new(x, y)))
+            .filter(|&x| x != p)
+            .collect();
+        expected.sort();
+        assert_eq!(around, expected);
+
+        let facing: Vec<_> = p.iter_facing(Direction::Left).collect();
+        assert_eq!(
+            facing,
+            vec![Vector::new(-1, 1), Vector::new(-1, 0), Vector::new(-1, -1)]
+        );
+    }
+
+    #[test]
+    fn point_surrounding_test() {
+        let corner = Point::new(0, 0);
+        assert_eq!(corner.iter_surrounding().count(), 3);
+        assert_eq!(corner.next8(Direction8::UpRight), Some(Point::new(1, 1)));
+        assert_eq!(corner.next8(Direction8::DownRight), None);
+        assert_eq!(
+            corner.iter_facing(Direction::Up).collect::<Vec<_>>(),
+            vec![Point::new(0, 1), Point::new(1, 1)]
+        );
+        assert_eq!(Point::new(1, 1).iter_surrounding().count(), 8);
+    }
+
+    #[test]
+    fn grid3_test() {
+        let mut grid = Grid3::new(Vector3::new(-1, 0, 2), Vector3::new(1, 1, 2), 0);
+        assert_eq!(grid.size(), (3, 2, 1));
+        grid[Vector3::new(-1, 1, 2)] = 5;
+        assert_eq!(grid.get(Vector3::new(-1, 1, 2)), Some(&5));
+        assert_eq!(grid.get(Vector3::new(-2, 0, 2)), None);
+        assert_eq!(grid.get(Vector3::new(0, 0, 3)), None);
+        assert_eq!(grid.get(Vector3::new(0, 2, 2)), None);
+
+        let empty = Grid3::new(Vector3::new(0, 0, 0), Vector3::new(-1, 3, 3), 0);
+        assert_eq!(empty.size(), (0, 4, 4));
+        assert_eq!(empty.get(Vector3::default()), None);
+    }
+
+    #[test]
+    fn vector3_test() {
+        let a = Vector3::new(1, -2, 3);
+        let b = Vector3::new(-1, 0, 5);
+        assert_eq!(a + b, Vector3::new(0, -2, 8));
+        assert_eq!(a - b, Vector3::new(2, -2, -2));
+        assert_eq!(-a * 2, Vector3::new(-2, 4, -6));
+        assert_eq!(a.manhattan_distance(&b), 6);
+        assert_eq!(
+            Vector3::bounds([a, b]),
+            Some((Vector3::new(-1, -2, 3), Vector3::new(1, 0, 5)))
+        );
+        assert_eq!(Vector3::bounds([]), None);
+
+        let p = Vector3::default();
+        assert_eq!(p.iter_adjacent().count(), 6);
+        assert!(p.iter_adjacent().all(|q| q.manhattan_distance(&p) == 1));
+        let mut around: Vec<_> = p.iter_surrounding().collect();
+        around.sort();
+        around.dedup();
+        assert_eq!(around.len(), 26);
+        assert!(!around.contains(&p));
+    }
+
+    #[test]
+    fn vector_point_test() {
+        assert_eq!(Point::try_from(Vector::new(3, 4)), Ok(Point::new(3, 4)));
+        assert!(Point::try_from(Vector::new(3, -1)).is_err());
+        assert_eq!(Vector::try_from(Point::new(3, 4)), Ok(Vector::new(3, 4)));
+        assert!(Vector::try_from(Point::new(usize::MAX, 0)).is_err());
+    }
 }
@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::solutions::{self, Day};
+
+/// Used when neither --inputs-dir nor AOC_INPUTS_DIR is set. Relative to the
+/// current directory.
+pub const DEFAULT_INPUTS_DIR: &str = "puzzle-inputs";
+
+/// Where puzzle inputs are loaded from.
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    /// Reads `YYYY/dayNN.txt` from the directory. If the file does not exist
+    /// the embedded input is used when the `embedded-inputs` feature is
+    /// enabled.
+    Dir(PathBuf),
+    /// The same input is used for every day.
+    Text(String),
+    /// The day's worked example with this name, see
+    /// [`Day::examples`](crate::solutions::Day::examples). Answers are checked
+    /// against the example's expected answers.
+    Example(String),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Dir(PathBuf::from(DEFAULT_INPUTS_DIR))
+    }
+}
+
+impl InputSource {
+    /// Reads inputs from `dir` or the default directory.
+    pub fn from_dir(dir: Option<PathBuf>) -> Self {
+        dir.map_or_else(Self::default, Self::Dir)
+    }
+
+    /// Loads the input for a day.
+    pub fn load(&self, year: usize, day: usize) -> Result<Cow<'_, str>> {
+        let solution = solutions::get(year, day)?;
+
+        match self {
+            Self::Dir(dir) => load_from_dir(dir, solution).map(Cow::Owned),
+            Self::Text(input) => Ok(Cow::Borrowed(input)),
+            Self::Example(name) => Ok(Cow::Borrowed(solution.example(name)?.input)),
+        }
+    }
+
+    /// The name of the example if this source reads examples.
+    pub fn example(&self) -> Option<&str> {
+        match self {
+            Self::Example(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+fn load_from_dir(dir: &Path, day: &Day) -> Result<String> {
+    let path = dir
+        .join(day.year.to_string())
+        .join(format!("{}.txt", day.name));
+    match std::fs::read_to_string(&path) {
+        Ok(x) => Ok(x),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => day
+            .input
+            .map(|x| x.to_owned())
+            .ok_or_else(|| anyhow!("input file not found: {}", path.display())),
+        Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_input_test() {
+        let source = InputSource::Dir(PathBuf::from("/nonexistent"));
+        let res = source.load(2022, 1);
+        if cfg!(feature = "embedded-inputs") {
+            assert!(res.is_ok());
+        } else {
+            assert!(res.unwrap_err().to_string().contains("2022/day01.txt"));
+        }
+    }
+
+    #[test]
+    fn text_input_test() {
+        let source = InputSource::Text("1\n2".to_owned());
+        assert_eq!(source.load(2022, 7).unwrap(), "1\n2");
+        assert!(source.load(2022, 26).is_err());
+        assert!(source.load(1999, 7).is_err());
+    }
+
+    #[test]
+    fn example_input_test() {
+        let source = InputSource::Example("small".to_owned());
+        assert_eq!(source.load(2022, 18).unwrap(), "1,1,1\n2,1,1");
+        let err = source.load(2022, 1).unwrap_err().to_string();
+        assert!(err.contains("known: example"));
+    }
+}
@@ -0,0 +1,269 @@
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::answers::Answer;
+use crate::cancel::TimedOut;
+use crate::inputs::InputSource;
+use crate::normalize::normalize;
+use crate::report::Record;
+use crate::runner::{self, DayOptions};
+use crate::usage::Usage;
+
+/// The subcommand [`run`] starts the child with, followed by the year, day
+/// and problem. The executable has to handle it by calling [`child_main`].
+pub const CHILD_COMMAND: &str = "run-isolated";
+
+/// The result of a problem solved by the child process.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    /// The answer to the problem.
+    pub answer: Answer,
+    /// How long parsing took.
+    pub parse: Duration,
+    /// How long solving took.
+    pub solve: Duration,
+    /// What solving used.
+    pub usage: Usage,
+}
+
+/// Encodes the result of a problem for the parent process. The first line is
+/// `parse_ns solve_ns kind`, the second the [`Usage`] and the rest is the
+/// answer.
+pub fn encode(outcome: &Outcome) -> String {
+    format!(
+        "{} {} {}\n{}\n{}",
+        outcome.parse.as_nanos(),
+        outcome.solve.as_nanos(),
+        outcome.answer.kind(),
+        outcome.usage,
+        outcome.answer
+    )
+}
+
+/// Decodes the output of [`encode`].
+pub fn decode(output: &str) -> Result<Outcome> {
+    let (header, rest) = output.split_once('\n').unwrap_or((output, ""));
+    let (usage, answer) = rest.split_once('\n').unwrap_or((rest, ""));
+
+    let fields: Vec<&str> = header.split(' ').collect();
+    let [parse, solve, kind] = fields[..] else {
+        return Err(anyhow!("malformed header: {:?}", header));
+    };
+
+    let nanos = |x: &str| -> Result<Duration> { Ok(Duration::from_nanos(x.parse()?)) };
+    Ok(Outcome {
+        answer: Answer::from_kind(kind, answer)?,
+        parse: nanos(parse)?,
+        solve: nanos(solve)?,
+        usage: usage.parse()?,
+    })
+}
+
+/// Solves a problem and prints the outcome for the parent process.
+pub fn child_main(
+    year: usize,
+    day: usize,
+    problem: usize,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<()> {
+    let (answer, timing) = runner::run_problem(year, day, problem, source, options)?;
+    let outcome = Outcome {
+        answer,
+        parse: timing.parse,
+        solve: timing.solve,
+        usage: timing.usage,
+    };
+    print!("{}", encode(&outcome));
+
+    Ok(())
+}
+
+/// Runs a single problem in a child process of the current executable. The
+/// child crashing, exiting with an error or printing garbage is recorded as a
+/// failure of the problem instead of taking down the caller. The child is
+/// killed if it runs longer than the timeout. Parameters are not passed on to
+/// the child.
+pub fn run(
+    year: usize,
+    day: usize,
+    problem: usize,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Record {
+    let record = Record {
+        year,
+        day,
+        problem,
+        ..Default::default()
+    };
+
+    match run_child(year, day, problem, source, options) {
+        Ok(outcome) => Record {
+            answer: Some(outcome.answer),
+            parse: Some(outcome.parse),
+            solve: Some(outcome.solve),
+            usage: Some(outcome.usage),
+            normalized: normalized(year, day, source, options),
+            ..record
+        },
+        Err(e) => record.failed(&e),
+    }
+}
+
+// Describes what the child changed when normalising the input. Normalising is
+// cheap next to solving, so the parent repeats it rather than extending the
+// protocol.
+fn normalized(
+    year: usize,
+    day: usize,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Option<String> {
+    let input = source.load(year, day).ok()?;
+    let (_, changes) = normalize(&input, options.normalize);
+    (!changes.is_empty()).then(|| changes.to_string())
+}
+
+fn run_child(
+    year: usize,
+    day: usize,
+    problem: usize,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<Outcome> {
+    let InputSource::Dir(dir) = source else {
+        return Err(anyhow!("isolation requires inputs from a directory"));
+    };
+
+    let exe = std::env::current_exe().context("failed to find the current executable")?;
+    let mut command = Command::new(exe);
+    command.arg("--inputs-dir").arg(dir);
+    if options.normalize.trim_blank_lines {
+        command.arg("--trim-blank-lines");
+    }
+    let child = command
+        .arg(CHILD_COMMAND)
+        .arg(year.to_string())
+        .arg(day.to_string())
+        .arg(problem.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to start child process")?;
+    let output = wait(child, options.timeout)?;
+
+    if !output.status.success() {
+        return Err(anyhow!("{}", failure(&output)));
+    }
+
+    let stdout = String::from_utf8(output.stdout).context("child output is not UTF-8")?;
+    decode(&stdout).context("invalid child output")
+}
+
+// Waits for the child to exit, killing it once the timeout has passed. The
+// output is read on separate threads so a chatty child can't fill the pipe
+// and block forever.
+fn wait(mut child: Child, timeout: Option<Duration>) -> Result<Output> {
+    let read_all = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = read_all(child.stdout.take().map(|x| Box::new(x) as _));
+    let stderr = read_all(child.stderr.take().map(|x| Box::new(x) as _));
+
+    let status = match timeout {
+        Some(timeout) => wait_timeout(&mut child, timeout)?,
+        None => child.wait()?,
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn wait_timeout(child: &mut Child, timeout: Duration) -> Result<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Err(TimedOut(timeout).into());
+        }
+
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+// Describes how the child failed along with whatever it printed to stderr.
+fn failure(output: &Output) -> String {
+    let status = match output.status.code() {
+        Some(code) => format!("exited with status {}", code),
+        None => crash_reason(&output.status),
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match stderr.trim() {
+        "" => status,
+        stderr => format!("{}: {}", status, stderr),
+    }
+}
+
+#[cfg(unix)]
+fn crash_reason(status: &ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+
+    match status.signal() {
+        Some(signal) => format!("crashed: killed by signal {}", signal),
+        None => format!("crashed: {}", status),
+    }
+}
+
+#[cfg(not(unix))]
+fn crash_reason(status: &ExitStatus) -> String {
+    format!("crashed: {}", status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let outcome = Outcome {
+            answer: Answer::Bitmap("#..\n.#.".parse().unwrap()),
+            parse: Duration::from_nanos(5),
+            solve: Duration::from_nanos(7),
+            usage: Usage {
+                peak_heap: Some(10),
+                allocations: Some(2),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(decode(&encode(&outcome)).unwrap(), outcome);
+    }
+
+    #[test]
+    fn malformed_test() {
+        assert!(decode("").is_err());
+        assert!(decode("1 2\n3").is_err());
+        assert!(decode("1 2 int\n0 0 0 - -\nx").is_err());
+        assert!(decode("1 2 int\n3").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Solutions to Advent of Code puzzles along with the tools used to run, verify
+//! and benchmark them.
+//!
+//! Every day is registered in [`solutions::SOLUTIONS`]. The [`runner`] module
+//! runs problems from the registry and records the answers, timings and
+//! failures. The `adventofcode2022` binary is a command line interface on top
+//! of this crate.
+//!
+//! ```no_run
+//! use adventofcode2022::inputs::InputSource;
+//! use adventofcode2022::runner::{self, DayOptions};
+//!
+//! let source = InputSource::default();
+//! let (answer, timing) = runner::run_problem(2022, 21, 1, &source, &DayOptions::default())?;
+//! println!("{} in {:?}", answer, timing.solve);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+
+#[macro_use]
+extern crate lazy_static;
+
+/// Answers returned by solutions and the checked in expected answers.
+pub mod answers;
+/// Repeated timing of problems and saved baselines.
+pub mod bench;
+/// Cooperative cancellation of long running solutions.
+pub mod cancel;
+/// Two dimensional grids and points.
+pub mod grid;
+/// Loading puzzle inputs.
+pub mod inputs;
+/// Running problems in child processes.
+pub mod isolate;
+/// Comparing answers across several input sets.
+pub mod matrix;
+/// Cleaning up line endings and other noise in inputs.
+pub mod normalize;
+/// Decoding letters drawn in bitmap answers.
+pub mod ocr;
+/// Puzzle constants solutions read while solving.
+pub mod params;
+/// nom helpers shared by the solution parsers.
+pub mod parser;
+/// Machine readable output of run results.
+pub mod report;
+/// Running problems from the registry.
+pub mod runner;
+/// Choosing which problems `run-all` runs.
+pub mod select;
+/// The registry of solutions for every day.
+pub mod solutions;
+/// Solve times saved from previous runs.
+pub mod timings;
+/// Heap, CPU and memory accounting.
+pub mod usage;
+/// Small helpers shared by solutions.
+pub mod utils;
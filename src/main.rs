@@ -1,24 +1,42 @@
-mod grid;
-mod parser;
-mod solutions;
-mod utils;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[macro_use]
-extern crate lazy_static;
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand};
 
-use std::time::{Duration, Instant};
+use adventofcode2022::answers::{Answer, Answers, Verdict};
+use adventofcode2022::inputs::InputSource;
+use adventofcode2022::params;
+use adventofcode2022::runner::{self, day_records, DayOptions};
+use adventofcode2022::{
+    bench, cancel, isolate, matrix, normalize, ocr, report, select, solutions, timings, usage,
+};
 
-use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand};
-use rayon::prelude::*;
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: usage::CountingAllocator = usage::CountingAllocator;
 
 #[derive(Parser)]
 #[command(name = "aoc2022")]
 #[command(author = "Stephen Weinberg")]
-#[command(about = "Solves Advent of Code 2022", long_about = None)]
+#[command(about = "Solves Advent of Code puzzles", long_about = None)]
 struct Cli {
     #[command(subcommand)]
     commands: Commands,
+    /// Directory containing the YYYY/dayNN.txt puzzle inputs.
+    #[arg(long, global = true, env = "AOC_INPUTS_DIR")]
+    inputs_dir: Option<PathBuf>,
+    /// Puzzle year. run, matrix and bench default to the latest year, run-all
+    /// and verify to every year.
+    #[arg(long, global = true)]
+    year: Option<usize>,
+    /// Remove blank lines at the end of inputs. A byte order mark and CRLF
+    /// line endings are always cleaned up.
+    #[arg(long, global = true)]
+    trim_blank_lines: bool,
 }
 
 #[derive(Subcommand)]
@@ -26,89 +44,488 @@ enum Commands {
     Run {
         day: usize,
         problem: usize,
+        /// Read the input from this file instead of the inputs directory, or
+        /// from stdin with -.
         #[arg(long)]
         input: Option<String>,
+        /// Have the solution read the input as it goes instead of loading
+        /// all of it first. Only days that scan their input once support
+        /// this.
+        #[arg(long, requires = "input", conflicts_with = "timeout")]
+        stream: bool,
+        /// Solve a worked example from the puzzle description instead of the
+        /// input and check its expected answer.
+        #[arg(
+            long,
+            conflicts_with = "input",
+            num_args = 0..=1,
+            default_missing_value = "example"
+        )]
+        example: Option<String>,
+        /// Set a parameter of the solution, e.g. --param row=10. See the
+        /// params command for what a day accepts.
+        #[arg(
+            long = "param",
+            value_name = "NAME=VALUE",
+            value_parser = params::parse_assignment
+        )]
+        params: Vec<(String, String)>,
+        /// Print bitmap answers as is instead of decoding their letters.
+        #[arg(long)]
+        raw: bool,
+        #[arg(long, value_enum, default_value_t)]
+        format: report::Format,
+        /// Give up on the problem after this long, e.g. 500ms, 10s or 2m.
+        #[arg(long, value_parser = cancel::parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    RunAll(RunAllArgs),
+    /// Lists the parameters a day's solution accepts.
+    Params {
+        day: usize,
     },
-    RunAll {
+    /// Runs a single problem for `run-all --isolate`.
+    #[command(name = isolate::CHILD_COMMAND, hide = true)]
+    RunIsolated {
+        year: usize,
+        day: usize,
+        problem: usize,
+    },
+    Verify {
         #[arg(long)]
         parallel: bool,
+        /// Path to an answers file for a single year. Defaults to the checked
+        /// in answers.
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Runs problems against every input set in a directory.
+    Matrix {
+        /// Directory with one subdirectory per input set, each laid out like
+        /// the inputs directory.
+        dir: PathBuf,
+        /// Problems to run as DAY or DAY-PROBLEM. Defaults to all.
+        problems: Vec<String>,
+    },
+    Bench {
+        /// Problems to benchmark as DAY or DAY-PROBLEM. Defaults to all.
+        problems: Vec<String>,
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+        #[arg(long, default_value_t = 2)]
+        warmup: usize,
+        /// Save the results as a baseline to this path.
+        #[arg(long)]
+        save: Option<String>,
+        /// Compare the results against a previously saved baseline.
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Percent slowdown of the median before flagging a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
     },
 }
 
+// Hides the Vec from clap so a list of days is parsed from a single value.
+type DayList = Vec<usize>;
+
+#[derive(Args)]
+struct RunAllArgs {
+    /// Run days in parallel, starting with the ones that took longest last
+    /// time.
+    #[arg(long)]
+    parallel: bool,
+    /// Number of threads to run on. Implies --parallel.
+    #[arg(long)]
+    threads: Option<usize>,
+    #[arg(long, value_enum, default_value_t)]
+    format: report::Format,
+    /// Run each problem in its own process so a crash only fails that
+    /// problem.
+    #[arg(long)]
+    isolate: bool,
+    /// Solve every worked example instead of the inputs and check their
+    /// expected answers. Timings are not saved.
+    #[arg(long, conflicts_with = "isolate")]
+    examples: bool,
+    /// Give up on a problem after this long, e.g. 500ms, 10s or 2m.
+    #[arg(long, value_parser = cancel::parse_timeout)]
+    timeout: Option<Duration>,
+    /// Only run these days, e.g. 1-10,15.
+    #[arg(long, value_parser = select::parse_days)]
+    days: Option<DayList>,
+    /// Only run this part of each day.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Skip these days, e.g. 19 or 15-16.
+    #[arg(long, value_parser = select::parse_days)]
+    exclude: Option<DayList>,
+    /// Only run the N slowest problems according to the saved timings.
+    #[arg(long)]
+    slowest: Option<usize>,
+    /// File the timings of each run are saved to. Defaults to timings.txt in
+    /// CARGO_TARGET_DIR or ./target.
+    #[arg(long, env = "AOC_TIMINGS_FILE")]
+    timings: Option<PathBuf>,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let source = InputSource::from_dir(cli.inputs_dir);
+
+    if let Some(year) = cli.year {
+        if !solutions::years().contains(&year) {
+            return Err(anyhow!("no solutions for {}", year));
+        }
+    }
+    let latest = cli.year.unwrap_or_else(solutions::latest_year);
+    let normalize = normalize::Options {
+        trim_blank_lines: cli.trim_blank_lines,
+    };
+    let options = DayOptions {
+        normalize,
+        ..Default::default()
+    };
 
     match cli.commands {
         Commands::Run {
             day,
             problem,
             input,
-        } => run(day, problem, input),
-        Commands::RunAll { parallel } => run_all(parallel),
+            stream,
+            example,
+            params,
+            raw,
+            format,
+            timeout,
+        } => {
+            let options = DayOptions {
+                params: params.into_iter().collect(),
+                normalize,
+                timeout,
+                ..Default::default()
+            };
+            if let (true, Some(path)) = (stream, &input) {
+                let mut reader = open_input(path)?;
+                let record = runner::stream_record(latest, day, problem, &mut reader, &options);
+                return show_record(record, raw, format);
+            }
+
+            let source = match (input, example) {
+                (Some(path), _) => InputSource::Text(read_input(&path)?),
+                (None, Some(name)) => InputSource::Example(name),
+                (None, None) => source,
+            };
+            let record = day_records(latest, day, &[problem], &source, &options).remove(0);
+            show_record(record, raw, format)
+        }
+        Commands::RunAll(args) => run_all(cli.year, args, normalize, &source),
+        Commands::Params { day } => list_params(latest, day),
+        Commands::RunIsolated { year, day, problem } => {
+            isolate::child_main(year, day, problem, &source, &options)
+        }
+        Commands::Verify { parallel, answers } => {
+            verify(cli.year, parallel, answers, &source, &options)
+        }
+        Commands::Matrix { dir, problems } => run_matrix(latest, &dir, &problems, &options),
+        Commands::Bench {
+            problems,
+            iterations,
+            warmup,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let settings = bench::Settings {
+                iterations,
+                warmup,
+                baseline: baseline.as_deref().map(bench::Baseline::load).transpose()?,
+                threshold,
+            };
+            bench(
+                latest,
+                &problems,
+                &settings,
+                save.as_deref(),
+                &source,
+                &options,
+            )
+        }
     }
 }
 
-fn run(day: usize, problem: usize, input: Option<String>) -> Result<()> {
-    let flag_input = input
-        .as_ref()
-        .map(|x| std::fs::read_to_string(x).context("failed to read input file"))
-        .transpose()?;
+// Opens the file given with --input, or stdin for -.
+fn open_input(path: &str) -> Result<Box<dyn BufRead>> {
+    match path {
+        "-" => Ok(Box::new(io::stdin().lock())),
+        path => {
+            let file = File::open(path).with_context(|| format!("failed to open {}", path))?;
+            Ok(Box::new(BufReader::new(file)))
+        }
+    }
+}
+
+// Reads all of the file given with --input, or stdin for -.
+fn read_input(path: &str) -> Result<String> {
+    let mut ret = String::new();
+    open_input(path)?
+        .read_to_string(&mut ret)
+        .context("failed to read input")?;
+    Ok(ret)
+}
+
+fn show_record(record: report::Record, raw: bool, format: report::Format) -> Result<()> {
+    match format {
+        report::Format::Text => {
+            if let Some(e) = &record.error {
+                return Err(anyhow!("{}", e));
+            }
+
+            let ans = record.answer.as_ref().unwrap();
+            match ans {
+                Answer::Bitmap(b) if !raw => match ocr::decode(b) {
+                    Ok(letters) => println!("{}", letters),
+                    Err(e) => {
+                        println!("{}", ans);
+                        eprintln!("\nfailed to decode bitmap: {:#}", e);
+                    }
+                },
+                _ => println!("{}", ans),
+            }
+            println!();
+            if let Some(parse) = record.parse {
+                println!("Parsed in {:?}", parse);
+            }
+            println!("Solved in {:?}", record.solve.unwrap());
+            println!("Used {}", usage::summary(&record.usage.unwrap()));
+            if let Some(changes) = &record.normalized {
+                println!("Normalized input: {}", changes);
+            }
+
+            return Ok(());
+        }
+        report::Format::Json => println!("{}", report::json(std::slice::from_ref(&record))),
+        report::Format::Csv => println!("{}", report::csv(std::slice::from_ref(&record))),
+    }
+
+    match record.error {
+        Some(e) => Err(anyhow!("{}", e)),
+        None => Ok(()),
+    }
+}
 
-    let (ans, duration) = run_problem(day, problem, flag_input.as_deref())?;
+fn list_params(year: usize, day: usize) -> Result<()> {
+    let params = solutions::get(year, day)?.solution.params();
+    if params.is_empty() {
+        println!("{} day {} has no parameters", year, day);
+    }
 
-    println!("{}", ans);
-    println!("\nComputed in {:?}", duration);
+    let width = params.iter().map(|x| x.name().len()).max().unwrap_or(0);
+    for param in params {
+        println!(
+            "{:width$}  {} (default {})",
+            param.name(),
+            param.help(),
+            param.default_value()
+        );
+    }
 
     Ok(())
 }
 
-fn run_all(parallel: bool) -> Result<()> {
-    let mut times = if parallel {
-        (1..=25)
-            .into_par_iter()
-            .flat_map(|day| [(day, 1), (day, 2)])
-            .map(|(day, problem)| (day, problem, run_problem(day, problem, None)))
-            .try_fold_with(Vec::new(), |mut acc, (day, problem, res)| {
-                acc.push((day, problem, res?.1));
-                anyhow::Ok(acc)
-            })
-            .try_reduce(Vec::new, |mut a, b| {
-                a.extend(b);
-                Ok(a)
-            })?
-    } else {
-        (1..=25)
-            .flat_map(|day| [(day, 1), (day, 2)])
-            .map(|(day, problem)| (day, problem, run_problem(day, problem, None)))
-            .try_fold(Vec::new(), |mut acc, (day, problem, res)| {
-                acc.push((day, problem, res?.1));
-                anyhow::Ok(acc)
-            })?
+fn run_all(
+    year: Option<usize>,
+    args: RunAllArgs,
+    normalize: normalize::Options,
+    source: &InputSource,
+) -> Result<()> {
+    let years = match year {
+        Some(year) => vec![year],
+        None => solutions::years(),
+    };
+
+    let timings_path = args.timings.unwrap_or_else(timings::default_path);
+    let mut timings = timings::Timings::load(&timings_path)?;
+    let selection = select::Selection {
+        days: args.days,
+        part: args.part.map(usize::from),
+        exclude: args.exclude.unwrap_or_default(),
+        slowest: args.slowest,
+    };
+    let problems = selection.select(&years, &timings)?;
+
+    let options = runner::RunAllOptions {
+        parallel: args.parallel || args.threads.is_some(),
+        threads: args.threads,
+        isolate: args.isolate,
+        timeout: args.timeout,
+        normalize,
+        examples: args.examples,
+        history: timings.clone(),
     };
+    let (mut records, totals) = runner::run_all(&problems, &options, source)?;
+
+    // Example timings say nothing about how long the inputs take.
+    if !args.examples {
+        timings.update(&records);
+        timings.save(&timings_path)?;
+    }
+
+    match args.format {
+        report::Format::Text => {
+            records.sort_by(|a, b| a.solve.cmp(&b.solve).reverse());
+            for r in &records {
+                let name = match &r.example {
+                    Some(example) => format!("{} {:2}-{} {}", r.year, r.day, r.problem, example),
+                    None => format!("{} {:2}-{}", r.year, r.day, r.problem),
+                };
+                match &r.error {
+                    Some(e) => println!("{}: ERROR {}", name, e),
+                    None => println!(
+                        "{}: {:?} (parse {:?}; {})",
+                        name,
+                        r.solve.unwrap(),
+                        r.parse.unwrap(),
+                        usage::summary(&r.usage.unwrap())
+                    ),
+                }
+            }
+        }
+        report::Format::Json => println!("{}", report::json(&records)),
+        report::Format::Csv => println!("{}", report::csv(&records)),
+    }
 
-    times.sort_by(|a, b| a.2.cmp(&b.2).reverse());
-    for (day, problem, duration) in &times {
-        println!("{:2}-{}: {:?}", day, problem, duration);
+    // Keep stdout parseable for the machine readable formats.
+    let summary = format!(
+        "Wall time {:?}, CPU time {:?}, summed task time {:?}",
+        totals.wall, totals.cpu, totals.tasks
+    );
+    match args.format {
+        report::Format::Text => println!("\n{}", summary),
+        _ => eprintln!("{}", summary),
+    }
+
+    let failed = records.iter().filter(|r| !r.success()).count();
+    if failed > 0 {
+        return Err(anyhow!("{} problems failed", failed));
     }
 
     Ok(())
 }
 
-fn run_problem(day: usize, problem: usize, input: Option<&str>) -> Result<(String, Duration)> {
-    let solution = solutions::SOLUTIONS
-        .get(&day)
-        .ok_or(anyhow!("unknown day: {}", day))?;
-    let problem_fn = match problem {
-        1 => solution.problem1,
-        2 => solution.problem2,
-        _ => return Err(anyhow!("unknown problem number: {}", problem)),
+fn run_matrix(year: usize, dir: &Path, problems: &[String], options: &DayOptions) -> Result<()> {
+    let sets = matrix::input_sets(dir)?;
+    if sets.is_empty() {
+        return Err(anyhow!("no input sets found in {}", dir.display()));
+    }
+
+    let problems = select::parse_problems(year, problems)?;
+    let records = matrix::run(year, &sets, &problems, options);
+
+    let names: Vec<String> = sets.into_iter().map(|(name, _)| name).collect();
+    println!("{}", matrix::render(&names, &records));
+
+    Ok(())
+}
+
+fn verify(
+    year: Option<usize>,
+    parallel: bool,
+    answers: Option<PathBuf>,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<()> {
+    // An answers file only covers a single year.
+    let years = match (year, &answers) {
+        (Some(year), _) => vec![year],
+        (None, Some(_)) => vec![solutions::latest_year()],
+        (None, None) => solutions::years(),
     };
 
-    let input = input.unwrap_or(solution.input);
+    let mut expected = HashMap::new();
+    for year in years {
+        let parsed = match &answers {
+            Some(path) => Answers::load(path)?,
+            None => Answers::for_year(year)?,
+        };
+        expected.insert(year, parsed);
+    }
+
+    let results = runner::verify(&expected, parallel, source, options);
+    for r in &results {
+        let name = format!("{} {:2}-{}", r.year, r.day, r.problem);
+        match &r.verdict {
+            Verdict::Pass => println!("{}: pass", name),
+            Verdict::Fail(diff) => println!("{}: FAIL\n{}", name, diff),
+            Verdict::Missing => println!("{}: missing", name),
+            Verdict::Error(e) => println!("{}: ERROR {}", name, e),
+        }
+    }
+
+    let count = |f: fn(&Verdict) -> bool| results.iter().filter(|r| f(&r.verdict)).count();
+    let passed = count(|x| *x == Verdict::Pass);
+    let failed = count(Verdict::failed);
+    let missing = count(|x| *x == Verdict::Missing);
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+
+    if failed > 0 {
+        return Err(anyhow!("{} problems failed verification", failed));
+    }
+
+    Ok(())
+}
+
+fn bench(
+    year: usize,
+    problems: &[String],
+    settings: &bench::Settings,
+    save: Option<&str>,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<()> {
+    let problems = select::parse_problems(year, problems)?;
+
+    println!(
+        "{:>5} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "", "parse", "min", "median", "mean", "p95", "stddev"
+    );
+    let results = bench::run(year, &problems, settings, source, options, |m| {
+        print!(
+            "{:2}-{}: {:>12.3?} {:>12.3?} {:>12.3?} {:>12.3?} {:>12.3?} {:>12.3?}",
+            m.day,
+            m.problem,
+            m.parse.median,
+            m.solve.min,
+            m.solve.median,
+            m.solve.mean,
+            m.solve.p95,
+            m.solve.stddev
+        );
+        match m.regression {
+            Some(change) => println!("  REGRESSION +{:.1}%", change),
+            None => println!(),
+        }
+    })?;
 
-    let start = Instant::now();
-    let ans = problem_fn(input).context("problemfn failed")?;
-    let end = Instant::now();
+    if let Some(path) = save {
+        results
+            .iter()
+            .copied()
+            .collect::<bench::Baseline>()
+            .save(path)?;
+    }
+
+    let regressions = results.iter().filter(|m| m.regression.is_some()).count();
+    if regressions > 0 {
+        return Err(anyhow!(
+            "{} problems regressed by more than {}%",
+            regressions,
+            settings.threshold
+        ));
+    }
 
-    return Ok((ans, end.duration_since(start)));
+    Ok(())
 }
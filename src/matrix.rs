@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+
+use crate::answers::Answer;
+use crate::inputs::InputSource;
+use crate::ocr;
+use crate::report::Record;
+use crate::runner::{day_records, DayOptions};
+
+/// Lists the input sets in `dir`. Every subdirectory is an input set named
+/// after the directory, holding that person's `YYYY/dayNN.txt` files.
+pub fn input_sets(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut ret = Vec::new();
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read input sets in {}", dir.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            ret.push((
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            ));
+        }
+    }
+
+    ret.sort();
+    Ok(ret)
+}
+
+/// Solves `problems`, given as (day, problem), of `year` with every input
+/// set in `sets` (see [`input_sets`]) in parallel. Each day's input is
+/// parsed once per set. Returns the records keyed by (set, day, problem)
+/// where the set is an index into `sets`, ready for [`render`].
+pub fn run(
+    year: usize,
+    sets: &[(String, PathBuf)],
+    problems: &[(usize, usize)],
+    options: &DayOptions,
+) -> HashMap<(usize, usize, usize), Record> {
+    let mut days: Vec<(usize, Vec<usize>)> = Vec::new();
+    for &(day, problem) in problems {
+        match days.iter_mut().find(|(d, _)| *d == day) {
+            Some((_, problems)) => problems.push(problem),
+            None => days.push((day, vec![problem])),
+        }
+    }
+
+    let jobs: Vec<(usize, &(usize, Vec<usize>))> = (0..sets.len())
+        .flat_map(|s| days.iter().map(move |d| (s, d)))
+        .collect();
+
+    jobs.par_iter()
+        .flat_map_iter(|&(s, (day, problems))| {
+            let source = InputSource::Dir(sets[s].1.clone());
+            day_records(year, *day, problems, &source, options)
+                .into_iter()
+                .map(move |r| ((s, r.day, r.problem), r))
+        })
+        .collect()
+}
+
+/// Renders one row per problem and one column per input set. Each cell shows
+/// the answer and solve time, or ERROR. The errors are listed in full after
+/// the table.
+pub fn render(sets: &[String], records: &HashMap<(usize, usize, usize), Record>) -> String {
+    let mut problems: Vec<(usize, usize)> = records.keys().map(|&(_, d, p)| (d, p)).collect();
+    problems.sort();
+    problems.dedup();
+
+    let cell = |set: usize, day: usize, problem: usize| -> String {
+        match records.get(&(set, day, problem)) {
+            None => String::new(),
+            Some(r) => match (&r.answer, &r.error) {
+                (Some(ans), None) => format!("{} ({:.1?})", short_answer(ans), r.solve.unwrap()),
+                _ => "ERROR".to_owned(),
+            },
+        }
+    };
+
+    let rows: Vec<Vec<String>> = problems
+        .iter()
+        .map(|&(d, p)| (0..sets.len()).map(|s| cell(s, d, p)).collect())
+        .collect();
+
+    let widths: Vec<usize> = (0..sets.len())
+        .map(|s| {
+            rows.iter()
+                .map(|r| r[s].len())
+                .chain([sets[s].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    let header: Vec<String> = sets
+        .iter()
+        .zip(&widths)
+        .map(|(name, &w)| format!("{:w$}", name))
+        .collect();
+    lines.push(
+        format!("{:5}  {}", "", header.join("  "))
+            .trim_end()
+            .to_owned(),
+    );
+
+    for (&(day, problem), row) in problems.iter().zip(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(c, &w)| format!("{:w$}", c))
+            .collect();
+        lines.push(
+            format!("{:2}-{}:  {}", day, problem, cells.join("  "))
+                .trim_end()
+                .to_owned(),
+        );
+    }
+
+    let mut errors: Vec<_> = records
+        .iter()
+        .filter_map(|(&(s, d, p), r)| Some((s, d, p, r.error.as_ref()?)))
+        .collect();
+    errors.sort();
+    if !errors.is_empty() {
+        lines.push(String::new());
+        for (s, d, p, e) in errors {
+            lines.push(format!("{} {:2}-{}: {}", sets[s], d, p, e.trim_end()));
+        }
+    }
+
+    lines.join("\n")
+}
+
+// Answers in a table cell must fit on one line.
+fn short_answer(ans: &Answer) -> String {
+    match ans {
+        Answer::Bitmap(b) => ocr::decode(b).unwrap_or_else(|_| "<bitmap>".to_owned()),
+        _ => ans.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn render_test() {
+        let ok = |day, answer: usize, ms| Record {
+            day,
+            problem: 1,
+            answer: Some(answer.into()),
+            solve: Some(Duration::from_millis(ms)),
+            ..Default::default()
+        };
+        let err = Record {
+            day: 22,
+            problem: 1,
+            error: Some("bad net".to_owned()),
+            ..Default::default()
+        };
+
+        let records = HashMap::from([
+            ((0, 1, 1), ok(1, 24000, 1)),
+            ((1, 1, 1), ok(1, 7, 12)),
+            ((0, 22, 1), ok(22, 5, 3)),
+            ((1, 22, 1), err),
+        ]);
+
+        assert_eq!(
+            render(&["alice".to_owned(), "bob".to_owned()], &records),
+            "       alice          bob
+ 1-1:  24000 (1.0ms)  7 (12.0ms)
+22-1:  5 (3.0ms)      ERROR
+
+bob 22-1: bad net"
+        );
+    }
+}
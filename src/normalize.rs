@@ -0,0 +1,344 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, BufRead, Read};
+
+const BOM: &[u8] = "\u{feff}".as_bytes();
+
+/// How inputs are cleaned up before they are parsed. A byte order mark is
+/// always stripped and CRLF line endings are always converted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Remove blank lines at the end of the input. Lines holding only ASCII
+    /// whitespace count as blank. The last line with content is kept as is,
+    /// line ending and trailing spaces included.
+    pub trim_blank_lines: bool,
+}
+
+/// What [`normalize`] changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    /// A byte order mark was stripped.
+    pub bom: bool,
+    /// Number of CRLF line endings converted to LF.
+    pub crlf: usize,
+    /// Number of blank lines trimmed from the end.
+    pub blank_lines: usize,
+}
+
+impl Changes {
+    /// Returns whether the input was left as is.
+    pub fn is_empty(&self) -> bool {
+        *self == Changes::default()
+    }
+}
+
+impl fmt::Display for Changes {
+    /// Lists the changes, e.g. `stripped BOM, converted 3 CRLF line endings`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut changes = Vec::new();
+        if self.bom {
+            changes.push("stripped BOM".to_owned());
+        }
+        if self.crlf > 0 {
+            changes.push(format!("converted {} CRLF line endings", self.crlf));
+        }
+        if self.blank_lines > 0 {
+            changes.push(format!("trimmed {} trailing blank lines", self.blank_lines));
+        }
+
+        match changes.is_empty() {
+            true => write!(f, "unchanged"),
+            false => write!(f, "{}", changes.join(", ")),
+        }
+    }
+}
+
+/// Cleans up an input so solutions only ever see LF line endings and no byte
+/// order mark. Returns the input borrowed if nothing had to change.
+pub fn normalize(input: &str, options: Options) -> (Cow<'_, str>, Changes) {
+    let mut changes = Changes::default();
+    let mut ret = Cow::Borrowed(input);
+
+    if let Some(rest) = input.strip_prefix('\u{feff}') {
+        changes.bom = true;
+        ret = Cow::Borrowed(rest);
+    }
+
+    changes.crlf = ret.matches("\r\n").count();
+    if changes.crlf > 0 {
+        ret = Cow::Owned(ret.replace("\r\n", "\n"));
+    }
+
+    if options.trim_blank_lines {
+        let content = ret
+            .trim_end_matches(|c: char| c.is_ascii_whitespace())
+            .len();
+        let end = blank_lines_start(&ret.as_bytes()[content..], content > 0) + content;
+        changes.blank_lines = ret[end..].split_inclusive('\n').count();
+        if changes.blank_lines > 0 {
+            ret = match ret {
+                Cow::Borrowed(x) => Cow::Borrowed(&x[..end]),
+                Cow::Owned(mut x) => {
+                    x.truncate(end);
+                    Cow::Owned(x)
+                }
+            };
+        }
+    }
+
+    (ret, changes)
+}
+
+// Where the trailing blank lines start in `trailing`, the whitespace after
+// the last other character. Spaces before the first line ending belong to
+// the last line with content, if there is one, and are kept.
+fn blank_lines_start(trailing: &[u8], after_content: bool) -> usize {
+    match trailing.iter().position(|&b| b == b'\n') {
+        _ if !after_content => 0,
+        Some(i) => i + 1,
+        None => trailing.len(),
+    }
+}
+
+/// Streaming version of [`normalize`] for inputs too large to hold in
+/// memory. Reads from `inner` as it goes and makes the same changes.
+pub struct Reader<R> {
+    inner: R,
+    options: Options,
+    changes: Changes,
+    started: bool,
+    // The first bytes read, held until it is clear whether they are a BOM.
+    lead: Vec<u8>,
+    // A CR at the end of the last chunk that may start a CRLF.
+    pending_cr: bool,
+    // Whitespace since the last other character, held back while trimming
+    // in case it turns out to be trailing.
+    held: Vec<u8>,
+    // Whether anything other than whitespace has been read.
+    content: bool,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Normalises what is read from `inner`.
+    pub fn new(inner: R, options: Options) -> Self {
+        Reader {
+            inner,
+            options,
+            changes: Changes::default(),
+            started: false,
+            lead: Vec::new(),
+            pending_cr: false,
+            held: Vec::new(),
+            content: false,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// What has been changed so far. Complete once everything is read.
+    pub fn changes(&self) -> Changes {
+        self.changes
+    }
+
+    fn push(&mut self, b: u8) {
+        if !self.options.trim_blank_lines {
+            self.buf.push(b);
+        } else if b.is_ascii_whitespace() {
+            self.held.push(b);
+        } else {
+            self.content = true;
+            self.buf.append(&mut self.held);
+            self.buf.push(b);
+        }
+    }
+
+    fn finish(&mut self) {
+        if std::mem::take(&mut self.pending_cr) {
+            self.push(b'\r');
+        }
+
+        let end = blank_lines_start(&self.held, self.content);
+        // Called again by every read after the end, when nothing is held.
+        let blank_lines = self.held[end..].split_inclusive(|&b| b == b'\n').count();
+        if blank_lines > 0 {
+            self.changes.blank_lines = blank_lines;
+        }
+        self.held.truncate(end);
+        self.buf.append(&mut self.held);
+    }
+
+    fn refill(&mut self) -> io::Result<()> {
+        self.buf.clear();
+        self.pos = 0;
+
+        while self.buf.is_empty() {
+            let chunk = self.inner.fill_buf()?;
+            if chunk.is_empty() {
+                if !self.started {
+                    self.start();
+                }
+                self.finish();
+                return Ok(());
+            }
+
+            // Copied so the inner reader can be consumed before pushing.
+            let chunk = chunk.to_vec();
+            self.inner.consume(chunk.len());
+            if self.started {
+                chunk.into_iter().for_each(|b| self.process(b));
+            } else {
+                // A short first read can split the BOM, so wait until there
+                // are enough bytes to tell.
+                self.lead.extend(chunk);
+                if self.lead.len() >= BOM.len() || !BOM.starts_with(&self.lead) {
+                    self.start();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Strips the BOM from the first bytes and processes the rest.
+    fn start(&mut self) {
+        self.started = true;
+        let mut lead = std::mem::take(&mut self.lead);
+        if lead.starts_with(BOM) {
+            self.changes.bom = true;
+            lead.drain(..BOM.len());
+        }
+        lead.into_iter().for_each(|b| self.process(b));
+    }
+
+    fn process(&mut self, b: u8) {
+        if std::mem::take(&mut self.pending_cr) {
+            if b == b'\n' {
+                self.changes.crlf += 1;
+                self.push(b'\n');
+                return;
+            }
+            self.push(b'\r');
+        }
+
+        match b {
+            b'\r' => self.pending_cr = true,
+            b => self.push(b),
+        }
+    }
+}
+
+impl<R: BufRead> Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.fill_buf()?.read(buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Reader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.buf.len() {
+            self.refill()?;
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.buf.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_test() {
+        let options = Options::default();
+        let (input, changes) = normalize("a\nb\n", options);
+        assert!(matches!(input, Cow::Borrowed("a\nb\n")));
+        assert!(changes.is_empty());
+
+        let (input, changes) = normalize("\u{feff}a\r\nb\r\n\r\n", options);
+        assert_eq!(input, "a\nb\n\n");
+        assert_eq!(
+            changes.to_string(),
+            "stripped BOM, converted 3 CRLF line endings"
+        );
+    }
+
+    #[test]
+    fn trim_blank_lines_test() {
+        let options = Options {
+            trim_blank_lines: true,
+        };
+        let (input, changes) = normalize("a\nb\n  \n\n", options);
+        assert_eq!(input, "a\nb\n");
+        assert_eq!(changes.blank_lines, 2);
+
+        let (input, changes) = normalize("a\nb\n", options);
+        assert_eq!(input, "a\nb\n");
+        assert!(changes.is_empty());
+
+        let (input, changes) = normalize("a  \n\n", options);
+        assert_eq!(input, "a  \n");
+        assert_eq!(changes.blank_lines, 1);
+
+        let (input, changes) = normalize("a  ", options);
+        assert_eq!(input, "a  ");
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn unicode_whitespace_test() {
+        let options = Options {
+            trim_blank_lines: true,
+        };
+        // Non-breaking spaces and line separators are content.
+        for (input, expected) in [
+            ("a\n\u{a0}\n\n", "a\n\u{a0}\n"),
+            ("a\n\u{2028}\n", "a\n\u{2028}\n"),
+        ] {
+            let (buffered, _) = normalize(input, options);
+            let mut streamed = String::new();
+            Reader::new(input.as_bytes(), options)
+                .read_to_string(&mut streamed)
+                .unwrap();
+            assert_eq!(buffered, expected);
+            assert_eq!(streamed, expected);
+        }
+    }
+
+    #[test]
+    fn reader_test() {
+        let inputs = [
+            "a\nb\n",
+            "\u{feff}a\r\nb\r\n\r\n",
+            "a\nb\n  \n\n",
+            "a  \n\n",
+            "a \t",
+            " \n\n",
+            "a\r\n\r\n  \r\nb\r",
+            "\u{feff}1\r\n2\r\n",
+            "\u{feff}",
+            "\u{fe0f}x",
+            "",
+        ];
+        for trim_blank_lines in [false, true] {
+            let options = Options { trim_blank_lines };
+            for (input, capacity) in inputs.into_iter().flat_map(|x| [(x, 1), (x, 2), (x, 4)]) {
+                // Tiny buffers split CRLFs and the BOM across reads.
+                let inner = io::BufReader::with_capacity(capacity, input.as_bytes());
+                let mut reader = Reader::new(inner, options);
+                let mut output = String::new();
+                reader.read_to_string(&mut output).unwrap();
+
+                let (expected, changes) = normalize(input, options);
+                assert_eq!(output, expected, "{:?} {}", input, capacity);
+                assert_eq!(reader.changes(), changes, "{:?} {}", input, capacity);
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::answers::Bitmap;
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+// Glyphs are separated by one blank column.
+const GLYPH_STRIDE: usize = GLYPH_WIDTH + 1;
+
+// The standard Advent of Code 4x6 font. Not every letter has been seen in a
+// puzzle so the alphabet is incomplete.
+const FONT: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Decodes a bitmap of capital letters in the 4x6 Advent of Code font.
+pub fn decode(bitmap: &Bitmap) -> Result<String> {
+    let (width, height) = bitmap.size();
+    if height != GLYPH_HEIGHT {
+        bail!(
+            "bitmap is {} pixels tall, glyphs are {}",
+            height,
+            GLYPH_HEIGHT
+        );
+    }
+
+    let mut ret = String::new();
+    let mut x = 0;
+    while x + GLYPH_WIDTH <= width {
+        let glyph = glyph_at(bitmap, x);
+        let c = FONT
+            .iter()
+            .find(|(_, g)| *g == glyph)
+            .map(|&(c, _)| c)
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown glyph at column {} (letter {}):\n{}",
+                    x,
+                    ret.len() + 1,
+                    render_glyph(&glyph)
+                )
+            })?;
+
+        ret.push(c);
+        x += GLYPH_STRIDE;
+    }
+
+    // Anything left over must be blank padding.
+    let is_lit = |col: usize| (0..height).any(|y| bitmap.get(col, y) == Some(true));
+    if let Some(col) = (x..width).find(|&col| is_lit(col)) {
+        bail!("unknown partial glyph at column {}", col);
+    }
+
+    Ok(ret)
+}
+
+fn glyph_at(bitmap: &Bitmap, x: usize) -> String {
+    (0..GLYPH_HEIGHT)
+        .flat_map(|y| (x..x + GLYPH_WIDTH).map(move |x| (x, y)))
+        .map(|(x, y)| match bitmap.get(x, y) {
+            Some(true) => '#',
+            _ => '.',
+        })
+        .collect()
+}
+
+fn render_glyph(glyph: &str) -> String {
+    glyph
+        .as_bytes()
+        .chunks(GLYPH_WIDTH)
+        .map(|row| String::from_utf8_lossy(row).into_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_test() {
+        let b: Bitmap = ("####.####..##..####.###..#..#.###..####.
+#....#....#..#.#....#..#.#..#.#..#.#....
+###..###..#....###..#..#.#..#.#..#.###..
+#....#....#.##.#....###..#..#.###..#....
+#....#....#..#.#....#.#..#..#.#.#..#....
+####.#.....###.####.#..#..##..#..#.####.")
+            .parse()
+            .unwrap();
+
+        assert_eq!(decode(&b).unwrap(), "EFGERURE");
+    }
+
+    #[test]
+    fn unknown_glyph_test() {
+        let b: Bitmap = ("####.#..#
+#....#..#
+###..####
+#....#..#
+#....#..#
+####.#.##")
+            .parse()
+            .unwrap();
+
+        let err = decode(&b).unwrap_err().to_string();
+        assert!(err.starts_with("unknown glyph at column 5 (letter 2)"));
+    }
+}
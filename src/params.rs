@@ -0,0 +1,209 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+
+/// A puzzle constant a solution reads while solving, e.g. the row to look at
+/// or the number of rounds to simulate. Overridden with `--param name=value`
+/// or by an [`Example`](crate::solutions::Example) that uses smaller values
+/// than the real input.
+///
+/// Solutions read the value with [`Param::get`] on the thread the solution is
+/// called on. Work handed to other threads has to take the value along, the
+/// same as the [cancellation token](crate::cancel::current).
+pub struct Param<T> {
+    /// The name used to set the parameter.
+    pub name: &'static str,
+    /// The value used unless one is set.
+    pub default: T,
+    /// What the parameter controls.
+    pub help: &'static str,
+}
+
+impl<T> Param<T> {
+    /// Declares a parameter.
+    pub const fn new(name: &'static str, default: T, help: &'static str) -> Self {
+        Param {
+            name,
+            default,
+            help,
+        }
+    }
+}
+
+impl<T> Param<T>
+where
+    T: FromStr + Clone,
+    T::Err: Display,
+{
+    /// The value set for this parameter on the current thread, or the default.
+    pub fn get(&self) -> Result<T> {
+        CURRENT.with(|x| match x.borrow().0.get(self.name) {
+            Some(value) => parse(self.name, value),
+            None => Ok(self.default.clone()),
+        })
+    }
+}
+
+/// Object safe view of a [`Param`] used to list and check the parameters of
+/// a solution.
+pub trait AnyParam: Sync {
+    /// The name used to set the parameter.
+    fn name(&self) -> &'static str;
+    /// The default value.
+    fn default_value(&self) -> String;
+    /// What the parameter controls.
+    fn help(&self) -> &'static str;
+    /// Returns an error if `value` is not valid for the parameter.
+    fn check(&self, value: &str) -> Result<()>;
+}
+
+impl<T> AnyParam for Param<T>
+where
+    T: FromStr + Display + Sync,
+    T::Err: Display,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn default_value(&self) -> String {
+        self.default.to_string()
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn check(&self, value: &str) -> Result<()> {
+        parse::<T>(self.name, value).map(|_| ())
+    }
+}
+
+fn parse<T>(name: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| anyhow!("invalid value {:?} for parameter {}: {}", value, name, e))
+}
+
+/// Parameter values by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Values(BTreeMap<String, String>);
+
+impl Values {
+    /// Returns these values with anything missing taken from `defaults`.
+    pub fn or(&self, defaults: &Values) -> Values {
+        let mut ret = defaults.clone();
+        ret.0.extend(self.0.clone());
+        ret
+    }
+
+    /// Returns an error naming the first value that isn't in `schema` or
+    /// isn't valid for its parameter.
+    pub fn check(&self, schema: &[&dyn AnyParam]) -> Result<()> {
+        for (name, value) in &self.0 {
+            let param = schema.iter().find(|x| x.name() == name).ok_or_else(|| {
+                let names: Vec<&str> = schema.iter().map(|x| x.name()).collect();
+                match names.is_empty() {
+                    true => anyhow!("unknown parameter {}: there are no parameters", name),
+                    false => anyhow!("unknown parameter {} (known: {})", name, names.join(", ")),
+                }
+            })?;
+            param.check(value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Values {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Values(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl From<&[(&str, &str)]> for Values {
+    fn from(x: &[(&str, &str)]) -> Self {
+        x.iter().copied().collect()
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Values> = RefCell::new(Values::default());
+}
+
+/// Runs `f` with `values` as the parameter values of this thread.
+pub fn with_values<R>(values: Values, f: impl FnOnce() -> R) -> R {
+    let old = CURRENT.with(|x| x.replace(values));
+    let ret = f();
+    CURRENT.with(|x| x.replace(old));
+
+    ret
+}
+
+/// Parses a `name=value` assignment from the command line.
+pub fn parse_assignment(s: &str) -> Result<(String, String)> {
+    let (name, value) = s
+        .split_once('=')
+        .with_context(|| format!("expected name=value: {}", s))?;
+    Ok((name.trim().to_owned(), value.trim().to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROW: Param<isize> = Param::new("row", 2000000, "Row to look at");
+
+    #[test]
+    fn get_test() {
+        assert_eq!(ROW.get().unwrap(), 2000000);
+
+        let values = Values::from(&[("row", "10")][..]);
+        assert_eq!(with_values(values, || ROW.get().unwrap()), 10);
+
+        let values = Values::from(&[("row", "x")][..]);
+        assert!(with_values(values, || ROW.get()).is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let schema: &[&dyn AnyParam] = &[&ROW];
+        assert!(Values::from(&[("row", "-5")][..]).check(schema).is_ok());
+        assert!(Values::from(&[("row", "x")][..]).check(schema).is_err());
+
+        let err = Values::from(&[("rows", "1")][..])
+            .check(schema)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "unknown parameter rows (known: row)");
+    }
+
+    #[test]
+    fn or_test() {
+        let set = Values::from(&[("a", "1")][..]);
+        let defaults = Values::from(&[("a", "2"), ("b", "3")][..]);
+        assert_eq!(
+            set.or(&defaults),
+            Values::from(&[("a", "1"), ("b", "3")][..])
+        );
+    }
+
+    #[test]
+    fn parse_assignment_test() {
+        assert_eq!(
+            parse_assignment("row=10").unwrap(),
+            ("row".to_owned(), "10".to_owned())
+        );
+        assert!(parse_assignment("row").is_err());
+    }
+}
@@ -1,7 +1,13 @@
 use nom::combinator::all_consuming;
+use nom::error::{VerboseError, VerboseErrorKind};
+use nom::{Finish, Offset};
 use prelude::*;
+use std::io::BufRead;
 use std::str::FromStr;
 
+/// Everything the solution parsers use, re-exported for a single glob
+/// import.
+#[allow(unused_imports)]
 pub mod prelude {
     pub use nom::{
         branch::alt,
@@ -10,23 +16,133 @@ pub mod prelude {
             alpha1, alphanumeric1, anychar, char, digit1, line_ending, multispace0, one_of, space0,
             space1,
         },
-        combinator::{eof, into, map, map_res, opt, recognize, value, verify},
-        error::ParseError,
+        combinator::{cut, eof, into, map, map_res, opt, recognize, value, verify},
+        error::{context, ParseError},
         multi::{count, many1, many1_count, separated_list1},
         sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
-        AsChar, IResult, InputTakeAtPosition, Parser,
+        AsChar, InputTakeAtPosition, Parser,
     };
 
-    pub use super::{complete, int, uint, ws_all_consuming, ws_line};
+    pub use super::{complete, int, uint, ws_all_consuming, ws_line, Error, IResult};
 }
 
+/// The error of the solution parsers. Keeps where each failure happened and
+/// the [`context`](nom::error::context) labels of the parsers it happened in
+/// so [`describe`] can point at the problem.
+pub type Error<I> = VerboseError<I>;
+
+/// The result of the solution parsers, [`nom::IResult`] with [`Error`].
+pub type IResult<I, O, E = Error<I>> = nom::IResult<I, O, E>;
+
+/// Describes a parse failure in `input` with the line and column it happened
+/// at, the offending line with a caret under the column, and the parsers
+/// labelled with [`context`](nom::error::context) it happened in.
+pub fn describe(input: &str, e: &Error<&str>) -> String {
+    describe_at(input, e, 1)
+}
+
+/// Parses one line of a streamed input with `parser`, which has to consume
+/// all of it apart from surrounding spaces. Errors are described like
+/// [`describe`] with `number` as the line number.
+pub fn parse_line<'a, O, P>(parser: P, line: &'a str, number: usize) -> anyhow::Result<O>
+where
+    P: nom::Parser<&'a str, O, Error<&'a str>>,
+{
+    all_consuming(delimited(space0, parser, space0))(line)
+        .finish()
+        .map(|(_, x)| x)
+        .map_err(|e| anyhow::anyhow!("{}", describe_at(line, &e, number)))
+}
+
+/// Parses each non-blank line of a streamed input with `parser`, see
+/// [`parse_line`].
+pub fn parse_lines<'a, O: 'a>(
+    input: &'a mut dyn BufRead,
+    parser: fn(&str) -> IResult<&str, O>,
+) -> impl Iterator<Item = anyhow::Result<O>> + 'a {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(move |(i, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(parse_line(parser, &line, i + 1)),
+            Err(e) => Some(Err(e.into())),
+        })
+}
+
+// Describes a parse failure in `input`, which starts at line `first_line` of
+// the whole input.
+fn describe_at(input: &str, e: &Error<&str>, first_line: usize) -> String {
+    let Some(&(rest, ref kind)) = e.errors.first() else {
+        return "unknown error".to_owned();
+    };
+
+    let (line, column, text) = locate(input, rest);
+    let line = line + first_line - 1;
+    let expected = match kind {
+        VerboseErrorKind::Char(c) => format!("expected {:?}", c),
+        VerboseErrorKind::Nom(nom::error::ErrorKind::Eof) => "expected end of input".to_owned(),
+        VerboseErrorKind::Nom(kind) => format!("{} parser failed", kind.description()),
+        VerboseErrorKind::Context(x) => format!("invalid {}", x),
+    };
+    let found = match rest.lines().next() {
+        None => "end of input".to_owned(),
+        Some(x) => format!("{:?}", x),
+    };
+
+    let mut ret = format!(
+        "line {}, column {}: {}, found {}
+{}
+{:>width$}",
+        line,
+        column,
+        expected,
+        found,
+        text,
+        "^",
+        width = column
+    );
+    for (rest, kind) in &e.errors {
+        if let VerboseErrorKind::Context(name) = kind {
+            let (line, column, _) = locate(input, rest);
+            let line = line + first_line - 1;
+            ret += &format!(
+                "
+in {} at line {}, column {}",
+                name, line, column
+            );
+        }
+    }
+
+    ret
+}
+
+// Returns the 1-based line and column of the start of `rest`, which must be a
+// suffix of `input`, and the text of that line.
+fn locate<'a>(input: &'a str, rest: &str) -> (usize, usize, &'a str) {
+    let offset = input.offset(rest);
+    let line_start = input[..offset].rfind('\n').map_or(0, |x| x + 1);
+    let line_end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |x| offset + x);
+
+    let line = input[..offset].matches('\n').count() + 1;
+    let column = input[line_start..offset].chars().count() + 1;
+    (
+        line,
+        column,
+        input[line_start..line_end].trim_end_matches('\r'),
+    )
+}
+
+/// Parses an unsigned integer.
 pub fn uint<T: FromStr>(input: &str) -> IResult<&str, T> {
     let digits = is_a("0123456789");
     let mut parser = map_res(digits, |x: &str| x.parse());
     parser(input)
 }
 
-#[allow(dead_code)]
+/// Parses an integer with an optional leading `-`.
 pub fn int<T: FromStr>(input: &str) -> IResult<&str, T> {
     let digits = is_a("0123456789");
     let num = tuple((opt(tag("-")), digits));
@@ -34,7 +150,7 @@ pub fn int<T: FromStr>(input: &str) -> IResult<&str, T> {
     parser(input)
 }
 
-// Ensures that parser F
+/// Runs `parser` and requires that only whitespace follows it.
 pub fn complete<I, O, E, P>(parser: P) -> impl FnMut(I) -> IResult<I, O, E>
 where
     I: nom::InputLength + nom::InputTakeAtPosition + Clone,
@@ -45,6 +161,8 @@ where
     terminated(parser, tuple((multispace0, eof)))
 }
 
+/// Runs `parser` on the whole input, ignoring leading and trailing
+/// whitespace.
 pub fn ws_all_consuming<I, O, E, P>(parser: P) -> impl FnMut(I) -> IResult<I, O, E>
 where
     I: nom::InputLength + nom::InputTakeAtPosition + Clone,
@@ -55,6 +173,8 @@ where
     all_consuming(delimited(multispace0, parser, multispace0))
 }
 
+/// Parses a line with `parser`, ignoring spaces around it. The line must end
+/// with a line ending or the end of the input.
 pub fn ws_line<'a, O, E, P>(parser: P) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
     P: nom::Parser<&'a str, O, E>,
@@ -63,3 +183,55 @@ where
     let end_of_line = alt((line_ending, eof));
     delimited(space0, parser, tuple((space0, end_of_line)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
+        let pair = separated_pair(uint, char(','), uint);
+        let line = context("pair", preceded(tag("p "), cut(pair)));
+        ws_all_consuming(many1(ws_line(line)))(input)
+    }
+
+    #[test]
+    fn describe_test() {
+        let input = "p 1,2\np 3;4\n";
+        let e = match pairs(input) {
+            Err(nom::Err::Failure(e)) => e,
+            x => panic!("unexpected result: {:?}", x),
+        };
+
+        assert_eq!(
+            describe(input, &e),
+            "line 2, column 4: expected ',', found \";4\"
+p 3;4
+   ^
+in pair at line 2, column 1"
+        );
+    }
+
+    #[test]
+    fn describe_eof_test() {
+        let input = "p 1,2\nq\n";
+        let e = match pairs(input) {
+            Err(nom::Err::Error(e)) => e,
+            x => panic!("unexpected result: {:?}", x),
+        };
+
+        assert_eq!(
+            describe(input, &e),
+            "line 2, column 1: expected end of input, found \"q\"\nq\n^"
+        );
+    }
+
+    #[test]
+    fn parse_line_test() {
+        let pair = || separated_pair(uint::<u32>, char(','), uint);
+        assert_eq!(parse_line(pair(), " 3,4 ", 7).unwrap(), (3, 4));
+        assert_eq!(
+            parse_line(pair(), "3;4", 7).unwrap_err().to_string(),
+            "line 7, column 2: expected ',', found \";4\"\n3;4\n ^"
+        );
+    }
+}
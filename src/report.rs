@@ -0,0 +1,275 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::answers::Answer;
+use crate::cancel::TimedOut;
+use crate::usage::Usage;
+
+/// How run results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable text.
+    #[default]
+    Text,
+    /// A JSON array of records, see [`json`].
+    Json,
+    /// CSV with a header row, see [`csv`].
+    Csv,
+}
+
+/// The outcome of running a single problem.
+#[derive(Clone, Debug, Default)]
+pub struct Record {
+    /// The year of the puzzle.
+    pub year: usize,
+    /// The day number.
+    pub day: usize,
+    /// The problem number, 1 or 2.
+    pub problem: usize,
+    /// The name of the example solved instead of the input.
+    pub example: Option<String>,
+    /// What was changed when normalising the input, if anything.
+    pub normalized: Option<String>,
+    /// The answer if the problem was solved.
+    pub answer: Option<Answer>,
+    /// How long parsing took if the input was parsed.
+    pub parse: Option<Duration>,
+    /// How long solving took if the problem was solved.
+    pub solve: Option<Duration>,
+    /// What solving used if the problem was solved.
+    pub usage: Option<Usage>,
+    /// Why the problem failed.
+    pub error: Option<String>,
+    /// Whether the problem failed by running out of time.
+    pub timed_out: bool,
+}
+
+impl Record {
+    /// Returns whether the problem was solved.
+    pub fn success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Marks the record as failed with `e`.
+    pub fn failed(self, e: &anyhow::Error) -> Self {
+        Record {
+            error: Some(format!("{:#}", e)),
+            timed_out: e.downcast_ref::<TimedOut>().is_some(),
+            ..self
+        }
+    }
+}
+
+enum Value {
+    Null,
+    Bool(bool),
+    Int(u128),
+    Str(String),
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(x: Option<T>) -> Self {
+        x.map_or(Value::Null, |x| x.into())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(x: bool) -> Self {
+        Value::Bool(x)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(x: usize) -> Self {
+        Value::Int(x as u128)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(x: u64) -> Self {
+        Value::Int(x as u128)
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(x: Duration) -> Self {
+        Value::Int(x.as_nanos())
+    }
+}
+
+impl From<String> for Value {
+    fn from(x: String) -> Self {
+        Value::Str(x)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(x: &str) -> Self {
+        Value::Str(x.to_owned())
+    }
+}
+
+// The columns of a record in output order.
+fn fields(r: &Record) -> Vec<(&'static str, Value)> {
+    vec![
+        ("year", r.year.into()),
+        ("day", r.day.into()),
+        ("problem", r.problem.into()),
+        ("example", r.example.as_deref().into()),
+        ("kind", r.answer.as_ref().map(|x| x.kind()).into()),
+        ("answer", r.answer.as_ref().map(|x| x.to_string()).into()),
+        ("parse_ns", r.parse.into()),
+        ("solve_ns", r.solve.into()),
+        ("user_ns", r.usage.and_then(|x| x.user).into()),
+        ("system_ns", r.usage.and_then(|x| x.system).into()),
+        ("max_rss_bytes", r.usage.map(|x| x.max_rss).into()),
+        ("peak_heap_bytes", r.usage.and_then(|x| x.peak_heap).into()),
+        ("allocations", r.usage.and_then(|x| x.allocations).into()),
+        ("success", r.success().into()),
+        ("timed_out", r.timed_out.into()),
+        ("normalized", r.normalized.as_deref().into()),
+        ("error", r.error.as_deref().into()),
+    ]
+}
+
+/// Renders records as a JSON array with one record per line. Records are
+/// sorted by year, day, problem and example so the output can be diffed
+/// between runs.
+pub fn json(records: &[Record]) -> String {
+    let lines: Vec<String> = sorted(records)
+        .into_iter()
+        .map(|r| {
+            let fields: Vec<String> = fields(r)
+                .into_iter()
+                .map(|(name, v)| format!("{}:{}", json_str(name), json_value(&v)))
+                .collect();
+            format!("  {{{}}}", fields.join(","))
+        })
+        .collect();
+
+    format!("[\n{}\n]", lines.join(",\n"))
+}
+
+/// Renders records as CSV with a header row. Records are sorted by year, day,
+/// problem and example.
+pub fn csv(records: &[Record]) -> String {
+    let header: Vec<&str> = fields(&Record::default())
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    let mut lines = vec![header.join(",")];
+    lines.extend(sorted(records).into_iter().map(|r| {
+        let fields: Vec<String> = fields(r).iter().map(|(_, v)| csv_value(v)).collect();
+        fields.join(",")
+    }));
+
+    lines.join("\n")
+}
+
+fn sorted(records: &[Record]) -> Vec<&Record> {
+    let mut ret: Vec<_> = records.iter().collect();
+    ret.sort_by_key(|r| (r.year, r.day, r.problem, r.example.as_deref()));
+    ret
+}
+
+fn json_value(v: &Value) -> String {
+    match v {
+        Value::Null => "null".to_owned(),
+        Value::Bool(x) => x.to_string(),
+        Value::Int(x) => x.to_string(),
+        Value::Str(x) => json_str(x),
+    }
+}
+
+fn json_str(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len() + 2);
+    ret.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+
+    ret
+}
+
+fn csv_value(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::Bool(x) => x.to_string(),
+        Value::Int(x) => x.to_string(),
+        Value::Str(s) if s.contains([',', '"', '\n', '\r']) => {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        }
+        Value::Str(s) => s.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2022,
+                day: 10,
+                problem: 2,
+                example: None,
+                answer: Some(Answer::Text("a,\"b\"\nc".to_owned())),
+                parse: Some(Duration::from_nanos(5)),
+                solve: Some(Duration::from_nanos(7)),
+                usage: Some(Usage {
+                    peak_heap: None,
+                    allocations: None,
+                    user: Some(Duration::from_nanos(3)),
+                    system: Some(Duration::from_nanos(2)),
+                    max_rss: 4096,
+                }),
+                error: None,
+                timed_out: false,
+                normalized: Some("converted 2 CRLF line endings".to_owned()),
+            },
+            Record {
+                year: 2022,
+                day: 1,
+                problem: 1,
+                example: Some("small".to_owned()),
+                error: Some("timed out after 1s".to_owned()),
+                timed_out: true,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn json_test() {
+        assert_eq!(
+            json(&records()),
+            r#"[
+  {"year":2022,"day":1,"problem":1,"example":"small","kind":null,"answer":null,"parse_ns":null,"solve_ns":null,"user_ns":null,"system_ns":null,"max_rss_bytes":null,"peak_heap_bytes":null,"allocations":null,"success":false,"timed_out":true,"normalized":null,"error":"timed out after 1s"},
+  {"year":2022,"day":10,"problem":2,"example":null,"kind":"text","answer":"a,\"b\"\nc","parse_ns":5,"solve_ns":7,"user_ns":3,"system_ns":2,"max_rss_bytes":4096,"peak_heap_bytes":null,"allocations":null,"success":true,"timed_out":false,"normalized":"converted 2 CRLF line endings","error":null}
+]"#
+        );
+    }
+
+    #[test]
+    fn csv_test() {
+        assert_eq!(
+            csv(&records()),
+            "year,day,problem,example,kind,answer,parse_ns,solve_ns,user_ns,system_ns,max_rss_bytes,peak_heap_bytes,allocations,success,timed_out,normalized,error
+2022,1,1,small,,,,,,,,,,false,true,,timed out after 1s
+2022,10,2,,text,\"a,\"\"b\"\"\nc\",5,7,3,2,4096,,,true,false,converted 2 CRLF line endings,"
+        );
+    }
+}
@@ -0,0 +1,646 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use rayon::prelude::*;
+use rayon::ThreadPool;
+
+use crate::answers::{Answer, Answers, Verdict};
+use crate::cancel;
+use crate::inputs::InputSource;
+use crate::isolate;
+use crate::normalize::{self, normalize, Changes};
+use crate::params::{self, Values};
+use crate::report::Record;
+use crate::solutions::{self, DynSolution, ParsedInput};
+use crate::timings::Timings;
+use crate::usage::{self, Sampler, Usage};
+
+/// How long a problem took and what it used.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timing {
+    /// How long parsing took.
+    pub parse: Duration,
+    /// How long solving took.
+    pub solve: Duration,
+    /// What solving used.
+    pub usage: Usage,
+}
+
+/// Options for [`run_all`].
+#[derive(Clone, Debug, Default)]
+pub struct RunAllOptions {
+    /// Run the days on a thread pool, longest first.
+    pub parallel: bool,
+    /// Number of threads for `parallel`. Defaults to one per CPU.
+    pub threads: Option<usize>,
+    /// Run each problem in a child process, see [`isolate::run`].
+    pub isolate: bool,
+    /// Give up on a problem after this long.
+    pub timeout: Option<Duration>,
+    /// How inputs are cleaned up before parsing.
+    pub normalize: normalize::Options,
+    /// Solve every example of the selected days instead of the inputs. Only
+    /// problems an example has an expected answer for are run.
+    pub examples: bool,
+    /// Solve times from previous runs used to start the longest days first.
+    pub history: Timings,
+}
+
+/// Options for solving the problems of a single day.
+#[derive(Clone, Debug, Default)]
+pub struct DayOptions {
+    /// Parameter values for the solution.
+    pub params: Values,
+    /// How the input is cleaned up before parsing.
+    pub normalize: normalize::Options,
+    /// Give up on a problem after this long.
+    pub timeout: Option<Duration>,
+    /// The pool problems are solved on, including any rayon work they do
+    /// themselves. The global pool if `None`.
+    pub pool: Option<Arc<ThreadPool>>,
+}
+
+/// How long [`run_all`] took as a whole.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Totals {
+    /// Time from start to finish.
+    pub wall: Duration,
+    /// User and system CPU time of the process and its children.
+    pub cpu: Duration,
+    /// Parse and solve time summed over every problem. Roughly what running
+    /// serially would take.
+    pub tasks: Duration,
+}
+
+/// Runs the given (year, day, problem) and returns a record per problem.
+/// Failures are recorded rather than returned. Problems of the same day
+/// share the parsed input unless they are isolated.
+pub fn run_all(
+    problems: &[(usize, usize, usize)],
+    options: &RunAllOptions,
+    source: &InputSource,
+) -> Result<(Vec<Record>, Totals)> {
+    let pool = match options.parallel {
+        true => Some(Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(options.threads.unwrap_or(0))
+                .build()
+                .context("failed to start thread pool")?,
+        )),
+        false => None,
+    };
+
+    let records_for = |job: &Job| -> Vec<Record> {
+        let example = job.example.map(|x| InputSource::Example(x.to_owned()));
+        let source = example.as_ref().unwrap_or(source);
+
+        let day_options = DayOptions {
+            params: Values::default(),
+            normalize: options.normalize,
+            timeout: options.timeout,
+            pool: pool.clone(),
+        };
+
+        if options.isolate {
+            job.problems
+                .iter()
+                .map(|&problem| isolate::run(job.year, job.day, problem, source, &day_options))
+                .collect()
+        } else {
+            day_records(job.year, job.day, &job.problems, source, &day_options)
+        }
+    };
+
+    let mut days: Vec<Job> = Vec::new();
+    for &(year, day, problem) in problems {
+        match days.iter_mut().find(|x| (x.year, x.day) == (year, day)) {
+            Some(job) => job.problems.push(problem),
+            None => days.push(Job {
+                year,
+                day,
+                example: None,
+                problems: vec![problem],
+            }),
+        }
+    }
+    if options.examples {
+        days = example_jobs(&days)?;
+    }
+
+    let start = Instant::now();
+    let cpu_start = usage::cpu_time();
+
+    let records: Vec<Record> = match &pool {
+        Some(pool) => {
+            longest_first(&mut days, &options.history);
+
+            // Every thread takes the next day off the list so the longest
+            // days start first. The threads only wait while the problems are
+            // solved on the pool, which is also where the solutions' own
+            // rayon work runs, so the pool's size limits the work done. They
+            // can't be pool threads themselves, or a problem solved on a
+            // thread of its own under a timeout could wait for a pool thread
+            // stuck waiting for it.
+            let next = AtomicUsize::new(0);
+            let results = Mutex::new(Vec::new());
+            std::thread::scope(|s| {
+                for _ in 0..pool.current_num_threads() {
+                    s.spawn(|| {
+                        while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                            let records = records_for(day);
+                            results.lock().unwrap().extend(records);
+                        }
+                    });
+                }
+            });
+            results.into_inner().unwrap()
+        }
+        None => days.iter().flat_map(records_for).collect(),
+    };
+
+    let totals = Totals {
+        wall: start.elapsed(),
+        cpu: usage::cpu_time().saturating_sub(cpu_start),
+        tasks: records
+            .iter()
+            .map(|r| r.parse.unwrap_or_default() + r.solve.unwrap_or_default())
+            .sum(),
+    };
+
+    Ok((records, totals))
+}
+
+// The problems of a day solved with one parse of the input.
+struct Job {
+    year: usize,
+    day: usize,
+    example: Option<&'static str>,
+    problems: Vec<usize>,
+}
+
+// Replaces each day with a job per example, keeping the problems the example
+// has an answer for.
+fn example_jobs(days: &[Job]) -> Result<Vec<Job>> {
+    let mut ret = Vec::new();
+    for job in days {
+        for example in solutions::get(job.year, job.day)?.examples {
+            let problems: Vec<usize> = job
+                .problems
+                .iter()
+                .copied()
+                .filter(|&p| example.answer(p).is_some())
+                .collect();
+            if !problems.is_empty() {
+                ret.push(Job {
+                    example: Some(example.name),
+                    problems,
+                    ..*job
+                });
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+// Sorts the days by their expected duration, longest first. Days missing from
+// the history go first since nothing is known about them.
+fn longest_first(days: &mut [Job], history: &Timings) {
+    days.sort_by_cached_key(|job| {
+        let expected: Option<Duration> = job
+            .problems
+            .iter()
+            .map(|&p| history.get(job.year, job.day, p))
+            .sum();
+        Reverse(expected.unwrap_or(Duration::MAX))
+    });
+}
+
+/// The outcome of checking one problem in [`verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verification {
+    /// The year of the puzzle.
+    pub year: usize,
+    /// The day of the puzzle.
+    pub day: usize,
+    /// The problem number.
+    pub problem: usize,
+    /// How the answer compared with the expected answer.
+    pub verdict: Verdict,
+}
+
+/// Solves both problems of every day of the years in `expected` and checks
+/// the answers against that year's expected answers. With `parallel` the
+/// days are solved on the global thread pool. The results are ordered by
+/// year, day and problem.
+pub fn verify(
+    expected: &HashMap<usize, Answers>,
+    parallel: bool,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Vec<Verification> {
+    let mut years: Vec<usize> = expected.keys().copied().collect();
+    years.sort();
+    let days: Vec<(usize, usize)> = years
+        .iter()
+        .flat_map(|&year| solutions::days(year).into_iter().map(move |d| (year, d)))
+        .collect();
+
+    let check = |&(year, day): &(usize, usize)| -> Vec<Verification> {
+        day_records(year, day, &[1, 2], source, options)
+            .into_iter()
+            .map(|r| {
+                let verdict = match (&r.answer, r.error) {
+                    (_, Some(e)) => Verdict::Error(e),
+                    (Some(ans), None) => expected[&year].check(day, r.problem, ans),
+                    (None, None) => unreachable!("record without answer or error"),
+                };
+                Verification {
+                    year,
+                    day,
+                    problem: r.problem,
+                    verdict,
+                }
+            })
+            .collect()
+    };
+
+    match parallel {
+        true => days.par_iter().flat_map_iter(check).collect(),
+        false => days.iter().flat_map(check).collect(),
+    }
+}
+
+/// Parses the input and solves a single problem. The timeout of `options` is
+/// ignored.
+pub fn run_problem(
+    year: usize,
+    day: usize,
+    problem: usize,
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<(Answer, Timing)> {
+    let options = DayOptions {
+        timeout: None,
+        ..options.clone()
+    };
+    let (parse, _, mut results) = run_day(year, day, &[problem], source, &options)?;
+    let (ans, solve, usage) = results.pop().unwrap()?;
+
+    Ok((
+        ans,
+        Timing {
+            parse,
+            solve,
+            usage,
+        },
+    ))
+}
+
+/// Solves a single problem reading the input from `input` as it goes, see
+/// [`Solution::stream`](crate::solutions::Solution::stream). The input is
+/// normalised while it is read. Parsing is part of solving, so no parse
+/// duration is recorded. The timeout of `options` is ignored.
+pub fn stream_record(
+    year: usize,
+    day: usize,
+    problem: usize,
+    input: &mut dyn BufRead,
+    options: &DayOptions,
+) -> Record {
+    let record = Record {
+        year,
+        day,
+        problem,
+        ..Default::default()
+    };
+
+    match stream_problem(year, day, problem, input, options) {
+        Ok((answer, solve, usage, changes)) => Record {
+            answer: Some(answer),
+            solve: Some(solve),
+            usage: Some(usage),
+            normalized: (!changes.is_empty()).then(|| changes.to_string()),
+            ..record
+        },
+        Err(e) => record.failed(&e),
+    }
+}
+
+fn stream_problem(
+    year: usize,
+    day: usize,
+    problem: usize,
+    input: &mut dyn BufRead,
+    options: &DayOptions,
+) -> Result<(Answer, Duration, Usage, Changes)> {
+    let solution = solutions::get(year, day)?;
+    if !solution.solution.streaming() {
+        bail!("{} day {} does not support streaming", year, day);
+    }
+    options.params.check(solution.solution.params())?;
+
+    let mut input = normalize::Reader::new(input, options.normalize);
+    let sampler = Sampler::start();
+    let start = Instant::now();
+    let ans = params::with_values(options.params.clone(), || {
+        solution.solution.stream(problem, &mut input)
+    })
+    .context("problemfn failed")?;
+
+    Ok((ans, start.elapsed(), sampler.finish(), input.changes()))
+}
+
+/// Parses the input for a day once and solves each of `problems` with it.
+/// A parse failure is recorded against every problem. With an example
+/// source, an answer different from the example's expected answer is
+/// recorded as a failure and the parameters in `options` override the
+/// example's own.
+pub fn day_records(
+    year: usize,
+    day: usize,
+    problems: &[usize],
+    source: &InputSource,
+    options: &DayOptions,
+) -> Vec<Record> {
+    let record = |problem| Record {
+        year,
+        day,
+        problem,
+        example: source.example().map(|x| x.to_owned()),
+        ..Default::default()
+    };
+
+    let (parse, changes, results) = match run_day(year, day, problems, source, options) {
+        Ok(x) => x,
+        Err(e) => {
+            return problems
+                .iter()
+                .map(|&problem| record(problem).failed(&e))
+                .collect();
+        }
+    };
+
+    problems
+        .iter()
+        .zip(results)
+        .map(|(&problem, res)| match res {
+            Ok((answer, solve, usage)) => Record {
+                error: check_example(year, day, problem, source, &answer),
+                answer: Some(answer),
+                parse: Some(parse),
+                solve: Some(solve),
+                usage: Some(usage),
+                normalized: (!changes.is_empty()).then(|| changes.to_string()),
+                ..record(problem)
+            },
+            Err(e) => Record {
+                parse: Some(parse),
+                normalized: (!changes.is_empty()).then(|| changes.to_string()),
+                ..record(problem)
+            }
+            .failed(&e),
+        })
+        .collect()
+}
+
+// Compares the answer with the expected answer when solving an example.
+// Returns the error to record if they differ.
+fn check_example(
+    year: usize,
+    day: usize,
+    problem: usize,
+    source: &InputSource,
+    answer: &Answer,
+) -> Option<String> {
+    let example = solutions::get(year, day)
+        .ok()?
+        .example(source.example()?)
+        .ok()?;
+    match example.answer(problem) {
+        Some(expected) if !answer.matches(expected) => Some(format!(
+            "wrong answer for example {}: expected {}, got {}",
+            example.name, expected, answer
+        )),
+        _ => None,
+    }
+}
+
+// Parses the input for a day once and solves each of the requested problems
+// with it. Returns the parse duration, what normalising the input changed and
+// the result of each problem in order. The timeout applies to solving each
+// problem, not to parsing. Resource usage is sampled around solving each
+// problem.
+#[allow(clippy::type_complexity)]
+fn run_day(
+    year: usize,
+    day: usize,
+    problems: &[usize],
+    source: &InputSource,
+    options: &DayOptions,
+) -> Result<(Duration, Changes, Vec<Result<(Answer, Duration, Usage)>>)> {
+    let solution = solutions::get(year, day)?;
+    let input = source.load(year, day)?;
+    let (input, changes) = normalize(&input, options.normalize);
+
+    let params = match source.example() {
+        Some(name) => options
+            .params
+            .or(&Values::from(solution.example(name)?.params)),
+        None => options.params.clone(),
+    };
+    params.check(solution.solution.params())?;
+
+    let start = Instant::now();
+    let data = solution.solution.parse(&input).context("parse failed")?;
+    let parse_duration = start.elapsed();
+
+    let data = Arc::new(data);
+    let results = problems
+        .iter()
+        .map(|&problem| {
+            let sampler = Sampler::start();
+            let start = Instant::now();
+            let ans = solve(solution.solution, problem, &data, &params, options)?;
+            let elapsed = start.elapsed();
+            Ok((ans, elapsed, sampler.finish()))
+        })
+        .collect();
+
+    Ok((parse_duration, changes, results))
+}
+
+// Solves on the pool of `options`, if any. With a timeout the problem is
+// solved from its own thread so the runner can stop waiting even if the
+// solution never checks its cancellation token. Such a solution keeps running
+// in the background until it finishes.
+fn solve(
+    solution: &'static dyn DynSolution,
+    problem: usize,
+    data: &Arc<ParsedInput>,
+    params: &Values,
+    options: &DayOptions,
+) -> Result<Answer> {
+    let Some(timeout) = options.timeout else {
+        return install(options.pool.as_deref(), || {
+            params::with_values(params.clone(), || solution.solve(problem, data))
+        })
+        .context("problemfn failed");
+    };
+
+    let token = cancel::Token::default();
+    let (tx, rx) = mpsc::channel();
+    {
+        let token = token.clone();
+        let data = Arc::clone(data);
+        let params = params.clone();
+        let pool = options.pool.clone();
+        std::thread::spawn(move || {
+            let res = install(pool.as_deref(), || {
+                params::with_values(params, || {
+                    cancel::with_token(token, || solution.solve(problem, &data))
+                })
+            });
+            let _ = tx.send(res);
+        });
+    }
+
+    match rx.recv_timeout(timeout) {
+        Ok(res) => res.context("problemfn failed"),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(cancel::TimedOut(timeout).into())
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(anyhow!("problemfn panicked")),
+    }
+}
+
+// Runs `f` on `pool`, or on the current thread without one.
+fn install<R: Send>(pool: Option<&ThreadPool>, f: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_first_test() {
+        let mut history = Timings::default();
+        history
+            .results
+            .insert((2022, 1, 1), Duration::from_millis(1));
+        history
+            .results
+            .insert((2022, 1, 2), Duration::from_millis(1));
+        history
+            .results
+            .insert((2022, 15, 2), Duration::from_millis(700));
+        history
+            .results
+            .insert((2022, 19, 1), Duration::from_millis(5));
+
+        let job = |day, problems| Job {
+            year: 2022,
+            day,
+            example: None,
+            problems,
+        };
+        let mut days = vec![
+            job(1, vec![1, 2]),
+            job(19, vec![1]),
+            job(15, vec![2]),
+            job(16, vec![1]),
+        ];
+        longest_first(&mut days, &history);
+
+        let order: Vec<usize> = days.iter().map(|x| x.day).collect();
+        assert_eq!(order, vec![16, 15, 19, 1]);
+    }
+
+    #[test]
+    fn example_test() {
+        let small = InputSource::Example("small".to_owned());
+        let records = day_records(2022, 18, &[1, 2], &small, &DayOptions::default());
+        assert!(records.iter().all(|r| r.success()));
+
+        // Only correct with the example's own cube size.
+        let example = InputSource::Example("example".to_owned());
+        let records = day_records(2022, 22, &[2], &example, &DayOptions::default());
+        assert!(records[0].success());
+        assert!(matches!(&records[0].example, Some(x) if x == "example"));
+
+        let with_params = |params: &[(&str, &str)]| DayOptions {
+            params: Values::from(params),
+            ..Default::default()
+        };
+        let records = day_records(
+            2022,
+            22,
+            &[2],
+            &example,
+            &with_params(&[("cube_size", "5")]),
+        );
+        assert!(!records[0].success());
+
+        let records = day_records(2022, 22, &[2], &example, &with_params(&[("cube", "4")]));
+        assert!(records[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("unknown parameter cube"));
+    }
+
+    #[test]
+    fn stream_test() {
+        let mut input = "\u{feff}1000\r\n2000\r\n\r\n3000\r\n".as_bytes();
+        let record = stream_record(2022, 1, 2, &mut input, &DayOptions::default());
+        assert!(record.answer.as_ref().unwrap().matches("6000"));
+        assert_eq!(
+            record.normalized.as_deref(),
+            Some("stripped BOM, converted 4 CRLF line endings")
+        );
+
+        let record = stream_record(2022, 5, 1, &mut &b""[..], &DayOptions::default());
+        assert_eq!(
+            record.error.as_deref(),
+            Some("2022 day 5 does not support streaming")
+        );
+    }
+
+    #[test]
+    fn normalize_test() {
+        let source = InputSource::Text("\u{feff}1000\r\n2000\r\n\r\n3000\r\n".to_owned());
+        let records = day_records(2022, 1, &[1], &source, &DayOptions::default());
+        assert!(records[0].answer.as_ref().unwrap().matches("3000"));
+        assert_eq!(
+            records[0].normalized.as_deref(),
+            Some("stripped BOM, converted 4 CRLF line endings")
+        );
+    }
+
+    #[test]
+    fn pool_timeout_test() {
+        // Day 15 uses rayon itself, which must not wait on the one thread.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let options = DayOptions {
+            timeout: Some(Duration::from_secs(60)),
+            pool: Some(Arc::new(pool)),
+            ..Default::default()
+        };
+        let example = InputSource::Example("example".to_owned());
+        let records = day_records(2022, 15, &[1, 2], &example, &options);
+        assert!(records.iter().all(|r| r.success()));
+    }
+}
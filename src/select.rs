@@ -0,0 +1,162 @@
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::solutions;
+use crate::timings::Timings;
+
+/// Which problems `run-all` runs. Every problem of the given years is
+/// selected by default.
+#[derive(Clone, Debug, Default)]
+pub struct Selection {
+    /// Only run these days.
+    pub days: Option<Vec<usize>>,
+    /// Only run this part of each day.
+    pub part: Option<usize>,
+    /// Skip these days.
+    pub exclude: Vec<usize>,
+    /// Only run the N problems that were slowest in the saved timings.
+    pub slowest: Option<usize>,
+}
+
+impl Selection {
+    /// Returns the selected problems as (year, day, problem) in order.
+    pub fn select(&self, years: &[usize], timings: &Timings) -> Result<Vec<(usize, usize, usize)>> {
+        if let Some(days) = &self.days {
+            let known = |d: &usize| years.iter().any(|&y| solutions::get(y, *d).is_ok());
+            if let Some(day) = days.iter().find(|d| !known(d)) {
+                bail!("unknown day: {}", day);
+            }
+        }
+
+        let parts = match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+
+        let mut ret: Vec<(usize, usize, usize)> = years
+            .iter()
+            .flat_map(|&year| solutions::days(year).into_iter().map(move |d| (year, d)))
+            .filter(|(_, d)| self.days.as_ref().is_none_or(|x| x.contains(d)))
+            .filter(|(_, d)| !self.exclude.contains(d))
+            .flat_map(|(year, day)| parts.iter().map(move |&p| (year, day, p)))
+            .collect();
+
+        if let Some(n) = self.slowest {
+            if timings.results.is_empty() {
+                bail!("no saved timings to find the slowest problems, run run-all first");
+            }
+
+            let mut timed: Vec<_> = ret
+                .into_iter()
+                .filter_map(|(y, d, p)| Some((timings.get(y, d, p)?, (y, d, p))))
+                .collect();
+            timed.sort_by(|a, b| a.0.cmp(&b.0).reverse());
+            ret = timed.into_iter().take(n).map(|(_, x)| x).collect();
+            ret.sort();
+        }
+
+        Ok(ret)
+    }
+}
+
+/// Parses a list of days and ranges of days such as `1-10,15`.
+pub fn parse_days(s: &str) -> Result<Vec<usize>> {
+    let mut ret = Vec::new();
+    for part in s.split(',') {
+        let parsed = match part.split_once('-') {
+            Some((start, end)) => match (start.trim().parse(), end.trim().parse()) {
+                (Ok(start), Ok(end)) if start <= end => Some(start..=end),
+                _ => None,
+            },
+            None => part.trim().parse::<usize>().ok().map(|d| d..=d),
+        };
+        ret.extend(parsed.ok_or_else(|| anyhow!("invalid days: {}", part))?);
+    }
+
+    Ok(ret)
+}
+
+/// Parses problem selections such as `15` (both problems) and `15-2` into a
+/// list of (day, problem). An empty selection selects every problem of the
+/// year.
+pub fn parse_problems(year: usize, selection: &[String]) -> Result<Vec<(usize, usize)>> {
+    if selection.is_empty() {
+        let days = solutions::days(year);
+        return Ok(days.into_iter().flat_map(|d| [(d, 1), (d, 2)]).collect());
+    }
+
+    let mut ret = Vec::new();
+    for s in selection {
+        let parsed = match s.split_once('-') {
+            Some((day, problem)) => day.parse().and_then(|d| Ok(vec![(d, problem.parse()?)])),
+            None => s.parse().map(|d| vec![(d, 1), (d, 2)]),
+        };
+        ret.extend(parsed.with_context(|| format!("invalid problem selection: {}", s))?);
+    }
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn parse_days_test() {
+        assert_eq!(parse_days("1-3,15").unwrap(), vec![1, 2, 3, 15]);
+        assert_eq!(parse_days("7").unwrap(), vec![7]);
+        assert!(parse_days("3-1").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn select_test() {
+        let selection = Selection {
+            days: Some(vec![1, 2, 3]),
+            part: Some(2),
+            exclude: vec![2],
+            slowest: None,
+        };
+        assert_eq!(
+            selection.select(&[2022], &Timings::default()).unwrap(),
+            vec![(2022, 1, 2), (2022, 3, 2)]
+        );
+
+        let mut timings = Timings::default();
+        timings
+            .results
+            .insert((2022, 19, 1), Duration::from_millis(5));
+        timings
+            .results
+            .insert((2022, 15, 2), Duration::from_millis(700));
+        timings
+            .results
+            .insert((2022, 16, 2), Duration::from_millis(50));
+        let selection = Selection {
+            slowest: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            selection.select(&[2022], &timings).unwrap(),
+            vec![(2022, 15, 2), (2022, 16, 2)]
+        );
+
+        let selection = Selection {
+            days: Some(vec![26]),
+            ..Default::default()
+        };
+        assert!(selection.select(&[2022], &timings).is_err());
+    }
+
+    #[test]
+    fn parse_problems_test() {
+        let selection = ["15".to_owned(), "3-2".to_owned()];
+        assert_eq!(
+            parse_problems(2022, &selection).unwrap(),
+            vec![(15, 1), (15, 2), (3, 2)]
+        );
+        assert_eq!(parse_problems(2022, &[]).unwrap().len(), 50);
+        assert!(parse_problems(2022, &["x-1".to_owned()]).is_err());
+    }
+}
@@ -1,63 +1,309 @@
+use std::any::Any;
 use std::collections::HashMap;
+use std::io::BufRead;
 
+use anyhow::anyhow;
+
+use crate::answers::Answer;
+use crate::params::AnyParam;
+
+// Declares the day modules of a year and the year's registry entries. Used
+// once per year module as `days!(2022; day01, day02, ...)`.
 macro_rules! days {
-    ($($x:ident), *) => {
+    ($year:literal; $($x:ident), *) => {
         $(
-            mod $x;
+            #[doc = concat!("The solution for ", stringify!($x), ".")]
+            #[allow(missing_docs)]
+            pub mod $x;
         )*
 
-        const SOLUTIONS_ARR: &'static [Solution] = &[
+        /// Every day of the year.
+        pub const DAYS: &[$crate::solutions::Day] = &[
             $(
-                Solution {
-                    day: stringify!($x),
-                    problem1: $x::problem1,
-                    problem2: $x::problem2,
-                    input: include_str!(concat!("../puzzle-inputs/", stringify!($x), ".txt"))
+                $crate::solutions::Day {
+                    year: $year,
+                    day: $crate::solutions::day_number(stringify!($x)),
+                    name: stringify!($x),
+                    solution: &$x::Solver,
+                    examples: $x::EXAMPLES,
+                    #[cfg(feature = "embedded-inputs")]
+                    input: Some(include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/puzzle-inputs/",
+                        stringify!($year),
+                        "/",
+                        stringify!($x),
+                        ".txt"
+                    ))),
+                    #[cfg(not(feature = "embedded-inputs"))]
+                    input: None,
                 },
             )*
         ];
+    };
+    ($year:literal; $($x:ident,) *) => (days!($year; $($x),*););
+}
+
+// Declares the year modules and lists their days for the registry.
+macro_rules! years {
+    ($($x:ident), *) => {
+        $(
+            #[doc = concat!("The solutions for ", stringify!($x), ".")]
+            pub mod $x;
+        )*
 
+        const YEARS: &[&[Day]] = &[$($x::DAYS), *];
     };
-    ($($x:ident,) *) => (days!($($x),*););
 }
 
 lazy_static! {
-    pub static ref SOLUTIONS: HashMap<usize, &'static Solution> =
-        HashMap::from_iter(SOLUTIONS_ARR.iter().map(|x| (mod_name_to_num(x.day), x)));
+    /// Every solution keyed by (year, day).
+    pub static ref SOLUTIONS: HashMap<(usize, usize), &'static Day> = YEARS
+        .iter()
+        .flat_map(|days| days.iter())
+        .map(|x| ((x.year, x.day), x))
+        .collect();
+}
+
+/// Returns the years with solutions in ascending order.
+pub fn years() -> Vec<usize> {
+    let mut ret: Vec<usize> = YEARS.iter().filter_map(|x| Some(x.first()?.year)).collect();
+    ret.sort();
+    ret
+}
+
+/// Returns the most recent year with solutions.
+pub fn latest_year() -> usize {
+    *years().last().expect("no years registered")
+}
+
+/// Returns the days of a year with solutions in ascending order.
+pub fn days(year: usize) -> Vec<usize> {
+    let mut ret: Vec<usize> = SOLUTIONS
+        .keys()
+        .filter(|&&(y, _)| y == year)
+        .map(|&(_, d)| d)
+        .collect();
+    ret.sort();
+    ret
+}
+
+/// Looks up the solution for a day.
+pub fn get(year: usize, day: usize) -> anyhow::Result<&'static Day> {
+    SOLUTIONS
+        .get(&(year, day))
+        .copied()
+        .ok_or_else(|| anyhow!("unknown day: {} day {}", year, day))
+}
+
+/// Converts a module name such as `day21` to its day number.
+pub const fn day_number(name: &str) -> usize {
+    let bytes = name.as_bytes();
+    let mut ret = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            ret = ret * 10 + (bytes[i] - b'0') as usize;
+        }
+        i += 1;
+    }
+    ret
+}
+
+/// A day's solution. The input is parsed once and the result is shared by
+/// both problems.
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Parsed: Send + Sync + 'static;
+
+    /// Parses the puzzle input.
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error>;
+    /// Solves the first problem of the day.
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error>;
+    /// Solves the second problem of the day.
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error>;
+
+    /// The parameters the problems read, see [`Param`](crate::params::Param).
+    const PARAMS: &'static [&'static dyn AnyParam] = &[];
+
+    /// Whether the day implements [`Solution::stream`].
+    const STREAMING: bool = false;
+
+    /// Solves problem 1 or 2 reading the input as it goes instead of parsing
+    /// all of it first, so inputs too large to hold in memory can be solved.
+    /// Only implemented by days that scan their input once.
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let _ = (problem, input);
+        Err(anyhow!("streaming is not supported"))
+    }
 }
 
-// converts "day21" to 21;
-fn mod_name_to_num(name: &str) -> usize {
-    name.chars()
-        .filter_map(|x| x.to_digit(10))
-        .fold(0, |acc, x| acc * 10 + x as usize)
+/// The output of [`DynSolution::parse`]. Holds a [`Solution::Parsed`].
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// Object safe version of [`Solution`] used by the registry. It is
+/// implemented for every `Solution`.
+pub trait DynSolution: Sync {
+    /// Parses the puzzle input.
+    fn parse(&self, input: &str) -> Result<ParsedInput, anyhow::Error>;
+    /// Solves problem 1 or 2 with input parsed by this solution.
+    fn solve(&self, problem: usize, data: &ParsedInput) -> Result<Answer, anyhow::Error>;
+    /// The parameters the problems read.
+    fn params(&self) -> &'static [&'static dyn AnyParam];
+    /// Whether the solution can [`stream`](DynSolution::stream) its input.
+    fn streaming(&self) -> bool;
+    /// Solves problem 1 or 2 reading the input as it goes.
+    fn stream(&self, problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error>;
 }
 
-pub type ProblemFn = fn(&str) -> Result<String, anyhow::Error>;
+impl<S: Solution + Sync> DynSolution for S {
+    fn parse(&self, input: &str) -> Result<ParsedInput, anyhow::Error> {
+        Ok(Box::new(<S as Solution>::parse(input)?))
+    }
+
+    fn solve(&self, problem: usize, data: &ParsedInput) -> Result<Answer, anyhow::Error> {
+        let data = data
+            .downcast_ref::<S::Parsed>()
+            .ok_or(anyhow!("parsed input is for a different solution"))?;
+
+        match problem {
+            1 => S::problem1(data),
+            2 => S::problem2(data),
+            _ => Err(anyhow!("unknown problem number: {}", problem)),
+        }
+    }
+
+    fn params(&self) -> &'static [&'static dyn AnyParam] {
+        S::PARAMS
+    }
+
+    fn streaming(&self) -> bool {
+        S::STREAMING
+    }
 
-pub struct Solution {
-    pub day: &'static str,
-    pub problem1: ProblemFn,
-    pub problem2: ProblemFn,
+    fn stream(&self, problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        match problem {
+            1 | 2 => S::stream(problem, input),
+            _ => Err(anyhow!("unknown problem number: {}", problem)),
+        }
+    }
+}
+
+/// A worked example from the puzzle description.
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    /// Name used to pick the example, `example` for the main one.
+    pub name: &'static str,
+    /// The example input.
     pub input: &'static str,
+    /// The expected answers to problems 1 and 2. `None` if the example
+    /// doesn't apply to that problem.
+    pub answers: [Option<&'static str>; 2],
+    /// Parameter values the example is solved with, e.g. a smaller search
+    /// area than the real input.
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+impl Example {
+    /// The expected answer to a problem.
+    pub fn answer(&self, problem: usize) -> Option<&'static str> {
+        self.answers.get(problem.checked_sub(1)?).copied().flatten()
+    }
+}
+
+/// A day in the registry.
+pub struct Day {
+    /// The year of the puzzle.
+    pub year: usize,
+    /// The day number.
+    pub day: usize,
+    /// The name of the module, e.g. `day01`.
+    pub name: &'static str,
+    /// The day's solution.
+    pub solution: &'static dyn DynSolution,
+    /// The input compiled into the binary with the `embedded-inputs` feature.
+    pub input: Option<&'static str>,
+    /// The worked examples from the puzzle description.
+    pub examples: &'static [Example],
+}
+
+impl Day {
+    /// Looks up an example by name.
+    pub fn example(&self, name: &str) -> anyhow::Result<&'static Example> {
+        self.examples
+            .iter()
+            .find(|x| x.name == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.examples.iter().map(|x| x.name).collect();
+                anyhow!(
+                    "{} day {} has no example named {} (known: {})",
+                    self.year,
+                    self.day,
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
 }
 
 #[macro_use]
 mod prelude {
+    pub use super::{Example, Solution};
+    pub use crate::answers::Answer;
+    pub use crate::params::{AnyParam, Param};
     pub use anyhow::{anyhow, bail, Context};
+    pub use std::io::BufRead;
 
     macro_rules! parse {
-        ($input:expr) => {
-            <::std::result::Result<_, _> as ::nom::Finish<_, _, _>>::finish(
-                parser::parse($input).map_err(|x| x.to_owned()),
-            )
-            .context("failed to parse input")?
-            .1
-        };
+        ($input:expr) => {{
+            let input: &str = $input;
+            <::std::result::Result<_, _> as ::nom::Finish<_, _, _>>::finish(parser::parse(input))
+                .map_err(|e| anyhow!("{}", $crate::parser::describe(input, &e)))
+                .context("failed to parse input")?
+                .1
+        }};
     }
 }
 
-days!(
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
-);
+years!(y2022);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_test() {
+        assert_eq!(day_number("day07"), 7);
+        assert_eq!(days(2022), (1..=25).collect::<Vec<_>>());
+        assert_eq!(get(2022, 21).unwrap().name, "day21");
+        assert!(get(2022, 26).is_err());
+    }
+
+    #[test]
+    fn stream_test() {
+        let streaming: Vec<usize> = days(2022)
+            .into_iter()
+            .filter(|&d| get(2022, d).unwrap().solution.streaming())
+            .collect();
+        assert_eq!(streaming, vec![1, 2, 3, 4, 6, 10, 25]);
+
+        for day in streaming {
+            let day = get(2022, day).unwrap();
+            for example in day.examples {
+                for problem in [1, 2] {
+                    let Some(expected) = example.answer(problem) else {
+                        continue;
+                    };
+                    let mut input = example.input.as_bytes();
+                    let ans = day.solution.stream(problem, &mut input).unwrap();
+                    assert!(ans.matches(expected), "{} {}", day.name, problem);
+                }
+            }
+        }
+        assert!(get(2022, 5)
+            .unwrap()
+            .solution
+            .stream(1, &mut &b""[..])
+            .is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::parser::parse_line;
+use crate::solutions::prelude::*;
+use crate::utils;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<Vec<usize>>;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        data.iter()
+            .map(|x| x.iter().sum::<usize>())
+            .max()
+            .ok_or(anyhow!("no elves"))
+            .map(Answer::from)
+    }
+
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let counts = data.iter().map(|x| x.iter().sum::<usize>());
+        let ans: usize = utils::top_n(counts, 3).sum();
+
+        Ok(ans.into())
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let n = match problem {
+            1 => 1,
+            _ => 3,
+        };
+
+        // Only the top n totals are kept, not every elf.
+        let mut top = Vec::with_capacity(n + 1);
+        let mut elf = None;
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            match line.trim() {
+                "" => keep_top(&mut top, n, elf.take()),
+                x => *elf.get_or_insert(0) += parse_line(parser::calories, x, i + 1)?,
+            }
+        }
+        keep_top(&mut top, n, elf);
+
+        if top.is_empty() {
+            bail!("no elves");
+        }
+        Ok(top.iter().sum::<usize>().into())
+    }
+}
+
+fn keep_top(top: &mut Vec<usize>, n: usize, total: Option<usize>) {
+    if let Some(total) = total {
+        top.push(total);
+        top.sort_unstable_by(|a, b| b.cmp(a));
+        top.truncate(n);
+    }
+}
+
+mod parser {
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Vec<usize>>> {
+        let elf = separated_list1(line_ending, calories);
+        let elves = separated_list1(tuple((line_ending, line_ending)), elf);
+        complete(elves)(input)
+    }
+
+    pub fn calories(input: &str) -> IResult<&str, usize> {
+        uint(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("24000"), Some("45000")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "24000")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "45000")
+    }
+}
@@ -1,25 +1,53 @@
+use crate::parser::parse_lines;
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
+pub struct Solver;
 
-    let ans = data
-        .iter()
-        .map(|(a, b)| score(b.as_shape(), b.as_shape().outcome(a)))
-        .sum::<u32>();
+impl Solution for Solver {
+    type Parsed = Vec<Round>;
+    const STREAMING: bool = true;
 
-    Ok(ans.to_string())
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = data.iter().map(score1).sum::<u32>();
+
+        Ok(ans.into())
+    }
+
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = data.iter().map(score2).sum::<u32>();
+
+        Ok(ans.into())
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let score: fn(&Round) -> u32 = match problem {
+            1 => score1,
+            _ => score2,
+        };
+
+        let mut ans = 0;
+        for round in parse_lines(input, parser::round) {
+            ans += score(&round?);
+        }
+
+        Ok(ans.into())
+    }
 }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
+pub type Round = (Shape, EncryptedValue);
 
-    let ans = data
-        .iter()
-        .map(|(a, b)| score(a.compliment(b.as_outcome().rev()), b.as_outcome()))
-        .sum::<u32>();
+// Scores a round reading the second column as the shape to play.
+fn score1((a, b): &Round) -> u32 {
+    score(b.as_shape(), b.as_shape().outcome(a))
+}
 
-    Ok(ans.to_string())
+// Scores a round reading the second column as the outcome to aim for.
+fn score2((a, b): &Round) -> u32 {
+    score(a.compliment(b.as_outcome().rev()), b.as_outcome())
 }
 
 fn score(player_move: Shape, outcome: Outcome) -> u32 {
@@ -133,7 +161,13 @@ mod parser {
     use super::*;
     use crate::parser::prelude::*;
 
-    pub fn parse(input: &str) -> IResult<&str, Vec<(Shape, EncryptedValue)>> {
+    pub fn parse(input: &str) -> IResult<&str, Vec<Round>> {
+        let parser = separated_list1(line_ending, round);
+
+        complete(parser)(input)
+    }
+
+    pub fn round(input: &str) -> IResult<&str, Round> {
         let shape_a = alt((
             value(Shape::Rock, tag("A")),
             value(Shape::Paper, tag("B")),
@@ -146,28 +180,34 @@ mod parser {
             value(EncryptedValue::Z, tag("Z")),
         ));
 
-        let inst = separated_pair(shape_a, space1, encrypted);
-        let parser = separated_list1(line_ending, inst);
-
-        complete(parser)(input)
+        separated_pair(shape_a, space1, encrypted)(input)
     }
 }
 
+const EXAMPLE_INPUT: &str = "A Y
+B X
+C Z";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("15"), Some("12")],
+    params: &[],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = "A Y
-B X
-C Z";
-
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "15")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "15")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "12")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "12")
     }
 }
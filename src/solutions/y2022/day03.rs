@@ -0,0 +1,121 @@
+use crate::solutions::prelude::*;
+
+use std::collections::HashSet;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<String>;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(input.split_whitespace().map(|x| x.to_owned()).collect())
+    }
+
+    fn problem1(sacks: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = sacks.iter().map(|x| misplaced(x)).sum::<u32>();
+
+        Ok(ans.into())
+    }
+
+    fn problem2(sacks: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let sacks: Vec<_> = sacks
+            .iter()
+            .map(|x| HashSet::<char>::from_iter(x.chars()))
+            .collect();
+
+        sacks
+            .chunks_exact(3)
+            .map(badge)
+            .try_fold(0, |acc, x| Ok(acc + x?))
+            .map(Answer::from)
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let mut ans = 0;
+        let mut group = Vec::with_capacity(3);
+        for line in input.lines() {
+            let line = line?;
+            let sack = line.trim();
+            if sack.is_empty() {
+                continue;
+            }
+
+            if problem == 1 {
+                ans += misplaced(sack);
+                continue;
+            }
+
+            group.push(HashSet::<char>::from_iter(sack.chars()));
+            if group.len() == 3 {
+                ans += badge(&group)?;
+                group.clear();
+            }
+        }
+
+        Ok(ans.into())
+    }
+}
+
+// Sums the priorities of the items in both compartments of a sack.
+fn misplaced(sack: &str) -> u32 {
+    let (a, b) = sack.split_at(sack.len() / 2);
+    let a_s = HashSet::<char>::from_iter(a.chars());
+    let b_s = HashSet::<char>::from_iter(b.chars());
+    a_s.intersection(&b_s).cloned().map(priority).sum()
+}
+
+// The priority of the item carried by every elf in a group of three.
+fn badge(group: &[HashSet<char>]) -> Result<u32, anyhow::Error> {
+    find_intersection(&group[0], &group[1], &group[2])
+        .map(priority)
+        .ok_or(anyhow!("group with no intersection"))
+}
+
+fn find_intersection(a: &HashSet<char>, b: &HashSet<char>, c: &HashSet<char>) -> Option<char> {
+    a.intersection(b)
+        .cloned()
+        .collect::<HashSet<char>>()
+        .intersection(c)
+        .cloned()
+        .next()
+}
+
+fn priority(c: char) -> u32 {
+    match c {
+        'a'..='z' => c as u32 - 'a' as u32 + 1,
+        'A'..='Z' => 27 + c as u32 - 'A' as u32,
+        _ => 0,
+    }
+}
+
+const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("157"), Some("70")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "157")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "70")
+    }
+}
@@ -0,0 +1,106 @@
+use crate::parser::parse_lines;
+use crate::solutions::prelude::*;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<(RangeInclusive, RangeInclusive)>;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = data.iter().filter(|(a, b)| a.fully_overlaps(b)).count();
+        Ok(ans.into())
+    }
+
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = data.iter().filter(|(a, b)| a.has_overlap(b)).count();
+        Ok(ans.into())
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let overlaps = match problem {
+            1 => RangeInclusive::fully_overlaps,
+            _ => RangeInclusive::has_overlap,
+        };
+
+        let mut ans = 0;
+        for pair in parse_lines(input, parser::pair) {
+            let (a, b) = pair?;
+            ans += overlaps(&a, &b) as usize;
+        }
+
+        Ok(ans.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RangeInclusive {
+    start: u32,
+    end: u32,
+}
+
+impl RangeInclusive {
+    fn fully_overlaps(&self, other: &Self) -> bool {
+        (self.start <= other.start && self.end >= other.end)
+            || (other.start <= self.start && other.end >= self.end)
+    }
+
+    fn has_overlap(&self, other: &Self) -> bool {
+        let (a, b) = (self.min(other), self.max(other));
+        a.end >= b.start
+    }
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<(RangeInclusive, RangeInclusive)>> {
+        let parser = separated_list1(line_ending, pair);
+        complete(parser)(input)
+    }
+
+    pub fn pair(input: &str) -> IResult<&str, (RangeInclusive, RangeInclusive)> {
+        let range = || {
+            map(separated_pair(uint, tag("-"), uint), |(start, end)| {
+                RangeInclusive { start, end }
+            })
+        };
+        separated_pair(range(), tag(","), range())(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("2"), Some("4")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "2")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "4")
+    }
+}
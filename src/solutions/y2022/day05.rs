@@ -2,22 +2,34 @@ use crate::solutions::prelude::*;
 
 use std::cmp::Ordering;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let (mut layout, moves) = parse!(input);
-    for m in &moves {
-        layout.apply_move(m, true).context("bad move")?;
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = (Layout, Vec<Move>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
     }
 
-    Ok(layout.top())
-}
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (layout, moves) = data;
+        let mut layout = layout.clone();
+        for m in moves {
+            layout.apply_move(m, true).context("bad move")?;
+        }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let (mut layout, moves) = parse!(input);
-    for m in &moves {
-        layout.apply_move(m, false).context("bad move")?;
+        Ok(layout.top().into())
     }
 
-    Ok(layout.top())
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (layout, moves) = data;
+        let mut layout = layout.clone();
+        for m in moves {
+            layout.apply_move(m, false).context("bad move")?;
+        }
+
+        Ok(layout.top().into())
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -91,7 +103,7 @@ mod parser {
                 uint::<usize>,
             )),
             |(_, count, _, from, _, to)| Move {
-                count: count,
+                count,
                 from: from - 1,
                 to: to - 1,
             },
@@ -104,7 +116,7 @@ mod parser {
 
     fn crate_layout(input: &str) -> IResult<&str, Layout> {
         let crate_ = delimited(char('['), one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ"), char(']'));
-        let crate_space = alt((map(crate_, |x| Some(x)), value(None, tag("   "))));
+        let crate_space = alt((map(crate_, Some), value(None, tag("   "))));
         let crate_line = separated_list1(char(' '), crate_space);
         let crate_lines = separated_list1(line_ending, crate_line);
         let numbers_line = many1(delimited(space0, uint::<usize>, space0));
@@ -130,11 +142,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "    [D]    
+const EXAMPLE_INPUT: &str = "    [D]    
 [N] [C]    
 [Z] [M] [P]
  1   2   3 
@@ -144,13 +152,26 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("CMZ"), Some("MCD")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "CMZ")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "CMZ")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "MCD")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "MCD")
     }
 }
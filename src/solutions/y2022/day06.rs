@@ -0,0 +1,101 @@
+use crate::solutions::prelude::*;
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = String;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn problem1(input: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = first_unique_str(input, 4).ok_or(anyhow!("no solution"))?;
+        Ok(ans.into())
+    }
+
+    fn problem2(input: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = first_unique_str(input, 14).ok_or(anyhow!("no solution"))?;
+        Ok(ans.into())
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let length = match problem {
+            1 => 4,
+            _ => 14,
+        };
+
+        let mut window = VecDeque::with_capacity(length);
+        let mut i = 0;
+        for b in input.bytes() {
+            let b = b?;
+            if b.is_ascii_whitespace() {
+                match i {
+                    0 => continue,
+                    _ => break,
+                }
+            }
+
+            i += 1;
+            if window.len() == length {
+                window.pop_front();
+            }
+            window.push_back(b);
+            if window.len() == length && unique(window.make_contiguous()) {
+                return Ok(i.into());
+            }
+        }
+
+        Err(anyhow!("no solution"))
+    }
+}
+
+fn first_unique_str(s: &str, length: usize) -> Option<usize> {
+    s.as_bytes()
+        .windows(length)
+        .enumerate()
+        .find(|(_, xs)| unique(xs))
+        .map(|(i, _)| i + length)
+}
+
+fn unique(xs: &[u8]) -> bool {
+    let mut s = HashSet::new();
+
+    for x in xs {
+        if !s.insert(x) {
+            return false;
+        }
+    }
+
+    true
+}
+
+const EXAMPLE_INPUT: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("7"), Some("19")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "7")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "19")
+    }
+}
@@ -2,30 +2,38 @@ use crate::solutions::prelude::*;
 
 use std::collections::HashMap;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
-    let file_tree = tree(data);
+pub struct Solver;
 
-    let ans: usize = directory_sizes(&file_tree)
-        .into_iter()
-        .filter(|&x| x < 100000)
-        .sum();
+impl Solution for Solver {
+    type Parsed = Vec<CommandBlock>;
 
-    Ok(ans.to_string())
-}
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let file_tree = tree(data.clone());
+
+        let ans: usize = directory_sizes(&file_tree)
+            .into_iter()
+            .filter(|&x| x < 100000)
+            .sum();
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
-    let file_tree = tree(data);
-    let dir_sizes = directory_sizes(&file_tree);
+        Ok(ans.into())
+    }
+
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let file_tree = tree(data.clone());
+        let dir_sizes = directory_sizes(&file_tree);
 
-    let root_size = *dir_sizes.iter().max().unwrap();
-    let free = 70000000 - root_size;
-    let needed = 30000000 - free;
+        let root_size = *dir_sizes.iter().max().unwrap();
+        let free = 70000000 - root_size;
+        let needed = 30000000 - free;
 
-    let ans = *dir_sizes.iter().filter(|&&x| x > needed).min().unwrap();
+        let ans = *dir_sizes.iter().filter(|&&x| x > needed).min().unwrap();
 
-    Ok(ans.to_string())
+        Ok(ans.into())
+    }
 }
 
 type InodeNumber = usize;
@@ -191,11 +199,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         $ cd /
         $ ls
         dir a
@@ -221,13 +225,26 @@ mod tests {
         7214296 k
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("95437"), Some("24933642")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "95437")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "95437")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "24933642")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "24933642")
     }
 }
@@ -0,0 +1,134 @@
+use crate::grid::{Direction, Grid, Point};
+use crate::solutions::prelude::*;
+
+use std::collections::HashSet;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(grid: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let mut visible = HashSet::new();
+
+        let (width, height) = grid.size();
+        for i in 0..height {
+            let left = Point::new(0, i);
+            let right = Point::new(width - 1, i);
+
+            mark_visible(&mut visible, grid, left, Direction::Right);
+            mark_visible(&mut visible, grid, right, Direction::Left);
+        }
+
+        for i in 0..width {
+            let bottom = Point::new(i, 0);
+            let top = Point::new(i, height - 1);
+
+            mark_visible(&mut visible, grid, bottom, Direction::Up);
+            mark_visible(&mut visible, grid, top, Direction::Down);
+        }
+
+        Ok(visible.len().into())
+    }
+
+    fn problem2(grid: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans = grid
+            .iter_points()
+            .map(|p| scenic_score(grid, p))
+            .max()
+            .unwrap();
+
+        Ok(ans.into())
+    }
+}
+
+fn scenic_score(grid: &Grid<u8>, p: Point) -> usize {
+    let height = *grid.get(p).unwrap();
+    Direction::iter()
+        .map(|d| count_line(grid, p, height, d))
+        .product()
+}
+
+fn count_line(grid: &Grid<u8>, start: Point, h: u8, d: Direction) -> usize {
+    let Some(next) = start.next(d) else { return 0 };
+
+    grid.iter_line(next, d)
+        .scan(false, |seen, (_, x)| {
+            if !*seen {
+                if *x >= h {
+                    *seen = true;
+                }
+                Some(x)
+            } else {
+                None
+            }
+        })
+        .count()
+}
+
+fn mark_visible(visible: &mut HashSet<Point>, grid: &Grid<u8>, start: Point, d: Direction) {
+    let mut cells = grid.iter_line(start, d);
+
+    let Some((first_point, &first_height)) = cells.next() else {
+        return;
+    };
+    let mut max_height = first_height;
+    visible.insert(first_point);
+
+    for (p, &h) in cells {
+        if h > max_height {
+            visible.insert(p);
+            max_height = h;
+        }
+    }
+}
+
+mod parser {
+    use crate::grid::Grid;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Grid<u8>> {
+        let digit = map(one_of::<_, _, Error<_>>("0123456789"), |c| {
+            c.to_digit(10).unwrap() as u8
+        });
+        let row = many1(digit);
+        let grid = map_res(many1(ws_line(row)), Grid::from_rows);
+        ws_all_consuming(grid)(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "
+        30373
+        25512
+        65332
+        33549
+        35390
+    ";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("21"), Some("8")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "21")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "8")
+    }
+}
@@ -0,0 +1,137 @@
+use crate::grid::{Direction, Vector};
+use crate::solutions::prelude::*;
+
+use std::collections::HashSet;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(instructions: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        Ok(num_tail_locations(instructions, 2).into())
+    }
+
+    fn problem2(instructions: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        Ok(num_tail_locations(instructions, 10).into())
+    }
+}
+
+fn num_tail_locations(instructions: &[Instruction], rope_length: usize) -> usize {
+    let mut state = State::new(rope_length);
+    let mut tail_locations = HashSet::new();
+
+    for inst in instructions {
+        for _ in 0..inst.steps {
+            state.step(inst.direction);
+            tail_locations.insert(state.tail());
+        }
+    }
+
+    tail_locations.len()
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    knots: Vec<Vector>,
+}
+
+impl State {
+    fn new(n: usize) -> Self {
+        State {
+            knots: vec![Vector::default(); n],
+        }
+    }
+
+    fn step(&mut self, d: Direction) {
+        // A knot that no longer touches the previous one moves one step
+        // towards it, diagonally if they aren't in the same row or column.
+        fn step_next_knot(prev: Vector, next: Vector) -> Vector {
+            if prev.chebyshev_distance(&next) > 1 {
+                next + (prev - next).signum()
+            } else {
+                next
+            }
+        }
+
+        let Some(head) = self.knots.first_mut() else {
+            return;
+        };
+        *head = head.step(d);
+
+        for i in 1..self.knots.len() {
+            let prev = self.knots[i - 1];
+            let next = self.knots[i];
+
+            self.knots[i] = step_next_knot(prev, next);
+        }
+    }
+
+    fn tail(&self) -> Vector {
+        self.knots[self.knots.len() - 1]
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Instruction {
+    direction: Direction,
+    steps: usize,
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
+        let direction = alt((
+            value(Direction::Up, char('U')),
+            value(Direction::Down, char('D')),
+            value(Direction::Left, char('L')),
+            value(Direction::Right, char('R')),
+        ));
+
+        let instruction = separated_pair(direction, space0, uint)
+            .map(|(direction, steps)| Instruction { direction, steps });
+        let instructions = many1(ws_line(instruction));
+        ws_all_consuming(instructions)(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "
+        R 4
+        U 4
+        L 3
+        D 1
+        R 4
+        D 1
+        L 5
+        R 2
+    ";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("13"), Some("1")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "13")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "1")
+    }
+}
@@ -1,53 +1,108 @@
+use crate::answers::Bitmap;
+use crate::parser::parse_lines;
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
-    let states: Vec<(usize, i32)> = MachineStateIter::new(&data).collect();
-    let total_cycles = states.last().unwrap().0;
-
-    let ans: i32 = (20..total_cycles + 1)
-        .step_by(40)
-        .map(|cycle| {
-            let loc = states.binary_search_by_key(&cycle, |x| x.0);
-            let i = match loc {
-                Ok(x) => x,
-                Err(x) => x,
-            };
-            cycle as i32 * states[i - 1].1
-        })
-        .sum();
+pub struct Solver;
 
-    Ok(ans.to_string())
-}
+impl Solution for Solver {
+    type Parsed = Vec<Instruction>;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
-    let states: Vec<(usize, i32)> = MachineStateIter::new(&data).collect();
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let states: Vec<(usize, i32)> = MachineStateIter::new(data).collect();
+        let total_cycles = states.last().unwrap().0;
 
-    let mut ans = String::new();
+        let ans: i32 = (20..total_cycles + 1)
+            .step_by(40)
+            .map(|cycle| {
+                let loc = states.binary_search_by_key(&cycle, |x| x.0);
+                let i = match loc {
+                    Ok(x) => x,
+                    Err(x) => x,
+                };
+                cycle as i32 * states[i - 1].1
+            })
+            .sum();
 
-    let mut x = 1;
-    let mut cur = 0;
+        Ok(ans.into())
+    }
 
-    for i in 0..240 {
-        if i % 40 == 0 && i != 0 {
-            ans.push('\n');
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let states: Vec<(usize, i32)> = MachineStateIter::new(data).collect();
+
+        let mut ans = Bitmap::new(40, 6);
+
+        let mut x = 1;
+        let mut cur = 0;
+
+        for i in 0..240 {
+            if i >= states[cur].0 {
+                x = states[cur].1;
+                cur += 1;
+            }
+
+            let pos = (i % 40) as i32;
+            ans.set(i % 40, i / 40, pos >= x - 1 && pos <= x + 1);
         }
 
-        if i >= states[cur].0 {
-            x = states[cur].1;
-            cur += 1;
+        Ok(ans.into())
+    }
+
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        let instructions = parse_lines(input, parser::instruction);
+
+        if problem == 1 {
+            let mut ans = 0;
+            execute(instructions, |cycle, x| {
+                if cycle % 40 == 20 {
+                    ans += cycle as i32 * x;
+                }
+            })?;
+            return Ok(ans.into());
+        }
+
+        let mut ans = Bitmap::new(40, 6);
+        let mut draw = |cycle: usize, x: i32| {
+            let i = cycle - 1;
+            let pos = (i % 40) as i32;
+            if i < 240 {
+                ans.set(i % 40, i / 40, pos >= x - 1 && pos <= x + 1);
+            }
+        };
+        let (cycles, x) = execute(instructions, &mut draw)?;
+        // The screen keeps drawing after the program ends.
+        for cycle in cycles + 1..=240 {
+            draw(cycle, x);
         }
 
-        let pos = (i % 40) as i32;
-        if pos >= x - 1 && pos <= x + 1 {
-            ans.push('#');
-        } else {
-            ans.push('.');
+        Ok(ans.into())
+    }
+}
+
+// Runs the program calling `tick` with the number of every cycle and the
+// value of x during it. Returns the number of cycles and the final x.
+fn execute(
+    instructions: impl Iterator<Item = Result<Instruction, anyhow::Error>>,
+    mut tick: impl FnMut(usize, i32),
+) -> Result<(usize, i32), anyhow::Error> {
+    let mut x = 1;
+    let mut cycle = 0;
+    for inst in instructions {
+        let inst = inst?;
+        for _ in 0..inst.cycles() {
+            cycle += 1;
+            tick(cycle, x);
+        }
+        if let Instruction::Addx(v) = inst {
+            x += v;
         }
     }
 
-    Ok(ans)
+    Ok((cycle, x))
 }
 
 struct MachineStateIter<'a> {
@@ -71,9 +126,7 @@ impl<'a> MachineStateIter<'a> {
 impl<'a> std::iter::Iterator for MachineStateIter<'a> {
     type Item = (usize, i32);
     fn next(&mut self) -> Option<Self::Item> {
-        if self.instructions.get(self.pc).is_none() {
-            return None;
-        }
+        self.instructions.get(self.pc)?;
 
         while let Some(inst) = self.instructions.get(self.pc) {
             self.pc += 1;
@@ -112,21 +165,19 @@ mod parser {
     use crate::parser::prelude::*;
 
     pub fn parse(input: &str) -> IResult<&str, Vec<Instruction>> {
-        let inst = alt((
-            value(Instruction::Noop, tag("noop")),
-            preceded(tag("addx "), int).map(|x| Instruction::Addx(x)),
-        ));
-
-        let parser = many1(ws_line(inst));
+        let parser = many1(ws_line(instruction));
         ws_all_consuming(parser)(input)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn instruction(input: &str) -> IResult<&str, Instruction> {
+        alt((
+            value(Instruction::Noop, tag("noop")),
+            preceded(tag("addx "), int).map(Instruction::Addx),
+        ))(input)
+    }
+}
 
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         addx 15
         addx -11
         addx 6
@@ -275,15 +326,38 @@ mod tests {
         noop
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [
+        Some("13140"),
+        Some(
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....",
+        ),
+    ],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "13140")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "13140")
     }
 
     #[test]
     fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(
-            problem2(EXAMPLE_INPUT).unwrap(),
+            Solver::problem2(&data).unwrap(),
             "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
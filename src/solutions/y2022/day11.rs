@@ -3,23 +3,35 @@ use std::collections::VecDeque;
 use crate::solutions::prelude::*;
 use crate::utils::top_n;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let monkeys = parse!(input);
-    let group = MonkeyGroup::new(monkeys, |x| x / 3);
-    simulate_monkeys(group, 20)
-}
+pub struct Solver;
+
+const ROUNDS1: Param<usize> = Param::new("rounds1", 20, "Rounds to simulate in problem 1");
+const ROUNDS2: Param<usize> = Param::new("rounds2", 10000, "Rounds to simulate in problem 2");
+
+impl Solution for Solver {
+    type Parsed = Vec<Monkey>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&ROUNDS1, &ROUNDS2];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let monkeys = parse!(input);
-    let common_multiple: u64 = monkeys.iter().map(|m| m.test_divisor).product();
-    let group = MonkeyGroup::new(monkeys, move |x| x % common_multiple);
-    simulate_monkeys(group, 10000)
+    fn problem1(monkeys: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let group = MonkeyGroup::new(monkeys.clone(), |x| x / 3);
+        simulate_monkeys(group, ROUNDS1.get()?)
+    }
+
+    fn problem2(monkeys: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let common_multiple: u64 = monkeys.iter().map(|m| m.test_divisor).product();
+        let group = MonkeyGroup::new(monkeys.clone(), move |x| x % common_multiple);
+        simulate_monkeys(group, ROUNDS2.get()?)
+    }
 }
 
 fn simulate_monkeys<F>(
     mut group: MonkeyGroup<F>,
     iterations: usize,
-) -> Result<String, anyhow::Error>
+) -> Result<Answer, anyhow::Error>
 where
     F: Fn(u64) -> u64,
 {
@@ -27,7 +39,7 @@ where
 
     let inspections = group.monkeys.iter().map(|m| m.num_inspections);
     let ans: u64 = top_n(inspections, 2).product();
-    Ok(ans.to_string())
+    Ok(ans.into())
 }
 
 struct MonkeyGroup<F: Fn(u64) -> u64> {
@@ -52,7 +64,7 @@ impl<F: Fn(u64) -> u64> MonkeyGroup<F> {
 
             let new_worry = (self.reducer)(self.monkeys[id].operation.apply(item.worry));
 
-            let next_monkey = match new_worry % self.monkeys[id].test_divisor == 0 {
+            let next_monkey = match new_worry.is_multiple_of(self.monkeys[id].test_divisor) {
                 true => self.monkeys[id].next_true,
                 false => self.monkeys[id].next_false,
             };
@@ -62,6 +74,7 @@ impl<F: Fn(u64) -> u64> MonkeyGroup<F> {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct Item {
     worry: u64,
 }
@@ -72,6 +85,7 @@ impl From<u64> for Item {
     }
 }
 
+#[derive(Clone, Debug)]
 pub struct Monkey {
     operation: Operation,
     test_divisor: u64,
@@ -115,17 +129,12 @@ mod parser {
             tag("Starting items: "),
             separated_list1(tag(", "), into(uint::<u64>)),
         );
-        let operand = || {
-            alt((
-                value(Operand::Old, tag("old")),
-                uint.map(|x| Operand::Value(x)),
-            ))
-        };
+        let operand = || alt((value(Operand::Old, tag("old")), uint.map(Operand::Value)));
         let operation = preceded(
             tag("Operation: new = old "),
             alt((
-                preceded(tag("+ "), operand()).map(|x| Operation::Add(x)),
-                preceded(tag("* "), operand()).map(|x| Operation::Mul(x)),
+                preceded(tag("+ "), operand()).map(Operation::Add),
+                preceded(tag("* "), operand()).map(Operation::Mul),
             )),
         );
         let divisor = preceded(tag("Test: divisible by "), uint);
@@ -158,11 +167,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         Monkey 0:
             Starting items: 79, 98
             Operation: new = old * 19
@@ -192,13 +197,26 @@ mod tests {
             If false: throw to monkey 1
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("10605"), Some("2713310158")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "10605")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "10605")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "2713310158")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "2713310158")
     }
 }
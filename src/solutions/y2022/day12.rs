@@ -0,0 +1,123 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::grid::{Direction, Grid, Point};
+use crate::solutions::prelude::*;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(grid: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let start = grid
+            .iter_points()
+            .find(|p| grid.get(*p).map(|&x| x == 'S').unwrap_or(false))
+            .ok_or(anyhow!("no starting location found"))?;
+
+        let ans = bfs(grid, [start])?;
+        Ok(ans.into())
+    }
+
+    fn problem2(grid: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let start_locations = grid
+            .iter_points()
+            .filter(|p| grid.get(*p).map(|&x| x == 'S' || x == 'a').unwrap_or(false));
+
+        let ans = bfs(grid, start_locations)?;
+
+        Ok(ans.into())
+    }
+}
+
+fn bfs<I>(grid: &Grid<char>, start_points: I) -> Result<usize, anyhow::Error>
+where
+    I: std::iter::IntoIterator<Item = Point>,
+{
+    let mut frontier = VecDeque::new();
+    let mut seen = HashSet::new();
+
+    for start in start_points {
+        frontier.push_back((start, 0));
+        seen.insert(start);
+    }
+
+    while let Some((p, steps)) = frontier.pop_front() {
+        let cur_height = match grid.get(p) {
+            Some('S') => 'a',
+            Some(x) => *x,
+            None => continue,
+        } as u32;
+
+        let next_directions = Direction::iter().filter_map(|d| p.next(d));
+        for p in next_directions {
+            let next_height = match grid.get(p) {
+                Some('E') => {
+                    if cur_height >= 'y' as u32 {
+                        return Ok(steps + 1);
+                    } else {
+                        continue;
+                    }
+                }
+                Some(x) => *x,
+                None => continue,
+            } as u32;
+
+            if next_height <= cur_height + 1 && seen.insert(p) {
+                frontier.push_back((p, steps + 1));
+            }
+        }
+    }
+
+    bail!("no solution");
+}
+
+mod parser {
+    use crate::grid::Grid;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Grid<char>> {
+        let row = map(alpha1, |x: &str| x.chars().collect::<Vec<_>>());
+        let grid = map_res(many1(ws_line(row)), Grid::from_rows);
+        ws_all_consuming(grid)(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("31"), Some("29")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "31")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "29")
+    }
+
+    #[test]
+    fn trailing_blank_line_test() {
+        let data = Solver::parse(&format!("{}\n\n", EXAMPLE_INPUT)).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "31")
+    }
+}
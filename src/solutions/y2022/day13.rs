@@ -2,43 +2,53 @@ use std::{cmp::Ordering, fmt::Write};
 
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let data = parse!(input);
-
-    let ans: usize = data
-        .iter()
-        .enumerate()
-        .filter_map(|(i, pair)| {
-            if pair.0.cmp(&pair.1).is_lt() {
-                Some(i + 1)
-            } else {
-                None
-            }
-        })
-        .sum();
+pub struct Solver;
 
-    Ok(ans.to_string())
-}
+impl Solution for Solver {
+    type Parsed = Vec<(List, List)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let mut modified_input = input.trim_end().to_owned();
-    modified_input += "\n\n[[2]]\n[[6]]";
-    let data = parse!(&modified_input);
-    let mut packets: Vec<_> = data.into_iter().flat_map(|(a, b)| [a, b]).collect();
-    packets.sort_unstable();
-
-    let divider_locations = packets
-        .iter()
-        .enumerate()
-        .filter(|(_, x)| {
-            let s = format!("{}", x);
-            s == "[[2]]" || s == "[[6]]"
-        })
-        .map(|(i, _)| i + 1);
-
-    let ans: usize = divider_locations.product();
-
-    Ok(ans.to_string())
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let ans: usize = data
+            .iter()
+            .enumerate()
+            .filter_map(|(i, pair)| {
+                if pair.0.cmp(&pair.1).is_lt() {
+                    Some(i + 1)
+                } else {
+                    None
+                }
+            })
+            .sum();
+
+        Ok(ans.into())
+    }
+
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let dividers = parse!("[[2]]\n[[6]]");
+        let mut packets: Vec<_> = data
+            .iter()
+            .chain(dividers.iter())
+            .flat_map(|(a, b)| [a, b])
+            .collect();
+        packets.sort_unstable();
+
+        let divider_locations = packets
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| {
+                let s = format!("{}", x);
+                s == "[[2]]" || s == "[[6]]"
+            })
+            .map(|(i, _)| i + 1);
+
+        let ans: usize = divider_locations.product();
+
+        Ok(ans.into())
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq)]
@@ -150,18 +160,11 @@ mod parser {
     }
 
     fn element(input: &str) -> IResult<&str, Element> {
-        alt((
-            uint::<u32>.map(|v| Element::Value(v)),
-            list.map(|l| Element::List(l)),
-        ))(input)
+        alt((uint::<u32>.map(Element::Value), list.map(Element::List)))(input)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         [1,1,3,1,1]
         [1,1,5,1,1]
         
@@ -187,13 +190,26 @@ mod tests {
         [1,[2,[3,[4,[5,6,0]]]],8,9]
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("13"), Some("140")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "13")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "13")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "140")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "140")
     }
 }
@@ -0,0 +1,168 @@
+use crate::grid::{ExpandingGrid, Vector};
+use crate::solutions::prelude::*;
+
+pub struct Solver;
+
+const SOURCE_X: Param<isize> = Param::new("source_x", 500, "Column the sand pours in from");
+const SOURCE_Y: Param<isize> = Param::new("source_y", 0, "Row the sand pours in from");
+
+// Where sand tries to fall, in order. Larger `y` is further down.
+const FALL: [Vector; 3] = [Vector::new(0, 1), Vector::new(-1, 1), Vector::new(1, 1)];
+
+impl Solution for Solver {
+    type Parsed = Vec<Line>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&SOURCE_X, &SOURCE_Y];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(lines: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (mut cave, bottom) = cave(lines);
+        let source = Vector::new(SOURCE_X.get()?, SOURCE_Y.get()?);
+
+        let mut count = 0;
+        while drop_sand(&mut cave, source, bottom, None) {
+            count += 1;
+        }
+
+        Ok(count.into())
+    }
+
+    fn problem2(lines: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (mut cave, bottom) = cave(lines);
+        let source = Vector::new(SOURCE_X.get()?, SOURCE_Y.get()?);
+        if source.y >= bottom + 2 {
+            bail!("sand source {:?} is not above the floor", source);
+        }
+
+        let mut count = 0;
+        while *cave.get(source) == GridValue::Air {
+            drop_sand(&mut cave, source, bottom, Some(bottom + 2));
+            count += 1;
+        }
+
+        Ok(count.into())
+    }
+}
+
+// Draws the rock lines, returning the cave and the y of its lowest rock.
+fn cave(lines: &[Line]) -> (ExpandingGrid<GridValue>, isize) {
+    let mut cave = ExpandingGrid::new(GridValue::Air);
+    for line in lines {
+        for point in line.points() {
+            cave.set(point, GridValue::Rock);
+        }
+    }
+    let bottom = cave.bounds().map_or(0, |(_, max)| max.y);
+
+    (cave, bottom)
+}
+
+// Drops a unit of sand from `source`. Returns false if it falls past
+// `bottom` into the abyss, which can't happen when there is a floor at
+// `floor`.
+fn drop_sand(
+    cave: &mut ExpandingGrid<GridValue>,
+    source: Vector,
+    bottom: isize,
+    floor: Option<isize>,
+) -> bool {
+    let mut cur = source;
+
+    'outer: loop {
+        if floor.is_none() && cur.y > bottom {
+            return false;
+        }
+
+        for offset in FALL {
+            let next = cur + offset;
+            if Some(next.y) != floor && *cave.get(next) == GridValue::Air {
+                cur = next;
+                continue 'outer;
+            }
+        }
+
+        cave.set(cur, GridValue::Sand);
+        return true;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GridValue {
+    Air,
+    Sand,
+    Rock,
+}
+
+pub struct Line {
+    a: Vector,
+    b: Vector,
+}
+
+impl Line {
+    fn new(a: Vector, b: Vector) -> Self {
+        assert!(a.x == b.x || a.y == b.y);
+        Line { a, b }
+    }
+
+    fn points(&self) -> impl Iterator<Item = Vector> {
+        let step = (self.b - self.a).signum();
+        let len = self.a.chebyshev_distance(&self.b);
+        let a = self.a;
+        (0..=len).map(move |i| a + step * i)
+    }
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Line>> {
+        let stream = separated_list1(tag(" -> "), point).map(|points| {
+            points
+                .windows(2)
+                .map(|w| Line::new(w[0], w[1]))
+                .collect::<Vec<_>>()
+        });
+
+        let parser =
+            many1(ws_line(stream)).map(|lines| lines.into_iter().flatten().collect::<Vec<_>>());
+        ws_all_consuming(parser)(input)
+    }
+
+    fn point(input: &str) -> IResult<&str, Vector> {
+        separated_pair(int, char(','), int)
+            .map(|(x, y)| Vector::new(x, y))
+            .parse(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "
+        498,4 -> 498,6 -> 496,6
+        503,4 -> 502,4 -> 502,9 -> 494,9
+    ";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("24"), Some("93")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "24")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "93")
+    }
+}
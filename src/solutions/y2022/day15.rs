@@ -1,46 +1,58 @@
+use crate::cancel;
+use crate::grid::Vector;
 use crate::solutions::prelude::*;
 
 use rayon::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    problem1_(input, 2000000)
-}
-
-fn problem1_(input: &str, row: isize) -> Result<String, anyhow::Error> {
-    let sensors = parse!(input);
+pub struct Solver;
 
-    let ranges = find_range_for_row(&sensors, row);
+const ROW: Param<isize> = Param::new("row", 2000000, "Row to count covered positions in");
+const BOUND: Param<isize> = Param::new(
+    "bound",
+    4000000,
+    "Largest x and y coordinate the distress beacon can have",
+);
 
-    let spaces_covered: usize = ranges.iter().map(|x| x.len()).sum();
-    let beacons = unique_beacons(&sensors)
-        .iter()
-        .filter(|b| b.y == row)
-        .count();
-    let ans = spaces_covered - beacons;
+impl Solution for Solver {
+    type Parsed = Vec<Sensor>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&ROW, &BOUND];
 
-    Ok(ans.to_string())
-}
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    problem2_(input, 4000000)
-}
+    fn problem1(sensors: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let row = ROW.get()?;
+        let ranges = find_range_for_row(sensors, row);
 
-fn problem2_(input: &str, max_coordinate: isize) -> Result<String, anyhow::Error> {
-    let sensors = parse!(input);
+        let spaces_covered: usize = ranges.iter().map(|x| x.len()).sum();
+        let beacons = unique_beacons(sensors)
+            .iter()
+            .filter(|b| b.y == row)
+            .count();
+        let ans = spaces_covered - beacons;
 
-    let (row, ranges) = (0..=max_coordinate)
-        .into_par_iter()
-        .map(|row| (row, find_range_for_row(&sensors, row)))
-        .find_any(|(_, ranges)| ranges.len() > 1)
-        .ok_or(anyhow!("no solution"))?;
+        Ok(ans.into())
+    }
 
-    let x = ranges[0].end;
-    let y = row;
-    let ans = x * 4000000 + y;
-    Ok(ans.to_string())
+    fn problem2(sensors: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let cancel = cancel::current();
+        let found = (0..=BOUND.get()?)
+            .into_par_iter()
+            .map(|row| (row, find_range_for_row(sensors, row)))
+            // Once cancelled any row will do so the search stops early.
+            .find_any(|(_, ranges)| cancel.is_cancelled() || ranges.len() > 1);
+        cancel.check()?;
+        let (row, ranges) = found.ok_or(anyhow!("no solution"))?;
+
+        let x = ranges[0].end;
+        let y = row;
+        let ans = x * 4000000 + y;
+        Ok(ans.into())
+    }
 }
 
-fn unique_beacons(sensors: &[Sensor]) -> Vec<Point> {
+fn unique_beacons(sensors: &[Sensor]) -> Vec<Vector> {
     let mut beacons: Vec<_> = sensors.iter().map(|s| s.closest_beacon).collect();
     beacons.sort_unstable();
     beacons.dedup();
@@ -66,7 +78,7 @@ fn find_range_for_row(sensors: &[Sensor], row: isize) -> Vec<Range> {
     let simplified_ranges = ranges.iter().fold(Vec::new(), |mut acc, r| {
         let Some(cur) = acc.last_mut() else {
             acc.push(*r);
-            return acc
+            return acc;
         };
 
         if let Some(combined) = cur.add(r) {
@@ -110,8 +122,8 @@ impl Range {
 }
 
 pub struct Sensor {
-    location: Point,
-    closest_beacon: Point,
+    location: Vector,
+    closest_beacon: Vector,
 }
 
 impl Sensor {
@@ -120,22 +132,6 @@ impl Sensor {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Point {
-    pub x: isize,
-    pub y: isize,
-}
-
-impl Point {
-    pub fn new(x: isize, y: isize) -> Self {
-        Point { x, y }
-    }
-
-    pub fn manhattan_distance(&self, other: &Self) -> isize {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
-
 mod parser {
     use super::*;
     use crate::parser::prelude::*;
@@ -147,7 +143,7 @@ mod parser {
                 tag(", "),
                 preceded(tag("y="), int),
             )
-            .map(|(x, y)| Point::new(x, y))
+            .map(|(x, y)| Vector::new(x, y))
         };
 
         let sensor = pair(
@@ -164,11 +160,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         Sensor at x=2, y=18: closest beacon is at x=-2, y=15
         Sensor at x=9, y=16: closest beacon is at x=10, y=16
         Sensor at x=13, y=2: closest beacon is at x=15, y=3
@@ -185,13 +177,40 @@ mod tests {
         Sensor at x=20, y=1: closest beacon is at x=15, y=3
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("26"), Some("56000011")],
+    params: &[("row", "10"), ("bound", "20")],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::{self, Values};
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1_(EXAMPLE_INPUT, 10).unwrap(), "26")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        let params = Values::from(EXAMPLES[0].params);
+        let ans = params::with_values(params, || Solver::problem1(&data));
+        assert_eq!(ans.unwrap(), "26")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2_(EXAMPLE_INPUT, 20).unwrap(), "56000011")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        let params = Values::from(EXAMPLES[0].params);
+        let ans = params::with_values(params, || Solver::problem2(&data));
+        assert_eq!(ans.unwrap(), "56000011")
+    }
+
+    #[test]
+    fn cancel_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        let token = cancel::Token::default();
+        token.cancel();
+
+        assert!(cancel::with_token(token, || Solver::problem2(&data)).is_err());
     }
 }
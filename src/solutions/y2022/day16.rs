@@ -1,35 +1,49 @@
 use std::{
     cmp::Reverse,
-    collections::{BinaryHeap, HashMap},
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
 };
 
+use crate::cancel;
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let valves = parse!(input);
-    let best_seen = open_valves(valves, 30)?;
+pub struct Solver;
 
-    let ans = best_seen
-        .iter()
-        .map(|(_, released)| released)
-        .max()
-        .ok_or(anyhow!("no paths followed"))?;
+const MINUTES1: Param<usize> = Param::new("minutes1", 30, "Minutes until the volcano erupts");
+const MINUTES2: Param<usize> =
+    Param::new("minutes2", 26, "Minutes left after teaching the elephant");
 
-    Ok(ans.to_string())
-}
+impl Solution for Solver {
+    type Parsed = Vec<Valve>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&MINUTES1, &MINUTES2];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let valves = parse!(input);
-    let best_seen = open_valves(valves, 26)?;
+    fn problem1(valves: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let best_seen = open_valves(valves.clone(), MINUTES1.get()?, &cancel::current())?;
 
-    let ans = best_seen
-        .iter()
-        .flat_map(|x| best_seen.iter().zip(std::iter::repeat(x)))
-        .filter_map(|(a, b)| Some(a.1 + b.1).filter(|_| a.0.is_disjoint(&b.0)))
-        .max()
-        .ok_or(anyhow!("no disjoint paths"))?;
+        let ans = best_seen
+            .iter()
+            .map(|&(_, released)| released)
+            .max()
+            .ok_or(anyhow!("no paths followed"))?;
 
-    Ok(ans.to_string())
+        Ok(ans.into())
+    }
+
+    fn problem2(valves: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let best_seen = open_valves(valves.clone(), MINUTES2.get()?, &cancel::current())?;
+
+        let ans = best_seen
+            .iter()
+            .flat_map(|x| best_seen.iter().zip(std::iter::repeat(x)))
+            .filter_map(|(a, b)| Some(a.1 + b.1).filter(|_| a.0.is_disjoint(&b.0)))
+            .max()
+            .ok_or(anyhow!("no disjoint paths"))?;
+
+        Ok(ans.into())
+    }
 }
 
 const START_VALVE: ValveName = ['A', 'A'];
@@ -42,7 +56,11 @@ fn simplify_valves(valves: Vec<Valve>) -> Vec<Valve2> {
     convert_valves(&trimmed_valves)
 }
 
-fn open_valves(valves: Vec<Valve>, time: usize) -> Result<Vec<(BitSet, usize)>, anyhow::Error> {
+fn open_valves(
+    valves: Vec<Valve>,
+    time: usize,
+    cancel: &cancel::Token,
+) -> Result<Vec<(BitSet, usize)>, anyhow::Error> {
     let valves = simplify_valves(valves);
 
     let start_index = valves
@@ -63,6 +81,8 @@ fn open_valves(valves: Vec<Valve>, time: usize) -> Result<Vec<(BitSet, usize)>,
     let mut best = HashMap::<BitSet, usize>::new();
 
     while let Some(mut state) = stack.pop() {
+        cancel.check()?;
+
         if state.time_remaining == 0 {
             continue;
         }
@@ -79,7 +99,7 @@ fn open_valves(valves: Vec<Valve>, time: usize) -> Result<Vec<(BitSet, usize)>,
 
         let next_states = cur_valve.tunnels.iter().filter_map(|&(v, d)| {
             if !state.opened.contains(v) {
-                let mut new_state = state.clone();
+                let mut new_state = state;
                 new_state.time_remaining = state.time_remaining.checked_sub(d)?;
                 new_state.cur_valve = v;
                 Some(new_state)
@@ -166,8 +186,8 @@ impl Graph {
 
         let mut tunnels = HashMap::new();
         while let Some(Reverse((cost, node))) = heap.pop() {
-            if !tunnels.contains_key(&node) {
-                tunnels.insert(node, cost);
+            if let Entry::Vacant(e) = tunnels.entry(node) {
+                e.insert(cost);
                 heap.extend(
                     self.nodes
                         .get(&node)
@@ -258,8 +278,7 @@ mod parser {
 
     fn valve(input: &str) -> IResult<&str, Valve> {
         let name = || pair(anychar, anychar).map(|(a, b)| [a, b]);
-        tuple((
-            tag("Valve "),
+        let valve = tuple((
             name(),
             tag(" has flow rate="),
             uint,
@@ -269,20 +288,17 @@ mod parser {
             )),
             separated_list1(tag(", "), name()),
         ))
-        .map(|(_, name, _, flow_rate, _, tunnels)| Valve {
+        .map(|(name, _, flow_rate, _, tunnels)| Valve {
             name,
             flow_rate,
             tunnels: tunnels.into_iter().map(|name| (name, 1)).collect(),
-        })
-        .parse(input)
+        });
+
+        context("valve", preceded(tag("Valve "), cut(valve))).parse(input)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
         Valve BB has flow rate=13; tunnels lead to valves CC, AA
         Valve CC has flow rate=2; tunnels lead to valves DD, BB
@@ -295,13 +311,26 @@ mod tests {
         Valve JJ has flow rate=21; tunnel leads to valve II
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("1651"), Some("1707")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "1651")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "1651")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "1707")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "1707")
     }
 }
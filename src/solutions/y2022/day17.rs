@@ -5,30 +5,45 @@ use ahash::HashMap;
 use crate::grid::{Direction, Point};
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let directions = parse!(input);
+pub struct Solver;
 
-    let mut dropper = Dropper::new(&directions);
+const ROCKS: Param<usize> = Param::new("rocks", 2022, "Rocks to drop in problem 1");
+const TARGET: Param<usize> = Param::new("target", 1000000000000, "Rocks to drop in problem 2");
 
-    Ok(dropper.iterate(2022).to_string())
-}
+impl Solution for Solver {
+    type Parsed = Vec<Direction>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&ROCKS, &TARGET];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    const TARGET: usize = 1000000000000;
-    let directions = parse!(input);
+    fn problem1(directions: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let mut dropper = Dropper::new(directions);
 
-    let (start, end) = find_periodic(&directions);
+        Ok(dropper.iterate(ROCKS.get()?).into())
+    }
+
+    fn problem2(directions: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let target = TARGET.get()?;
+        let (start, end) = find_periodic(directions);
+        if target < end {
+            let mut dropper = Dropper::new(directions);
+            return Ok(dropper.iterate(target).into());
+        }
 
-    let mut dropper = Dropper::new(&directions);
-    let preceding_height = dropper.iterate(end);
-    let block_size = end - start;
-    let block_height = dropper.iterate(block_size);
-    let remainder = (TARGET - end) % block_size;
-    let remainder_height = dropper.iterate(remainder);
+        let mut dropper = Dropper::new(directions);
+        let preceding_height = dropper.iterate(end);
+        let block_size = end - start;
+        let block_height = dropper.iterate(block_size);
+        let remainder = (target - end) % block_size;
+        let remainder_height = dropper.iterate(remainder);
 
-    let ans = preceding_height + block_height * ((TARGET - end) / block_size) + remainder_height;
+        let ans =
+            preceding_height + block_height * ((target - end) / block_size) + remainder_height;
 
-    Ok(ans.to_string())
+        Ok(ans.into())
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -42,7 +57,7 @@ impl Row {
     }
 
     fn set(&mut self, i: usize) {
-        self.0 = self.0 | (1 << i)
+        self.0 |= 1 << i
     }
 
     fn set_from(&mut self, other: &Self) {
@@ -161,7 +176,9 @@ impl<'a> Dropper<'a> {
             }
             self.direction_index += 1;
 
-            let Some(next_cur) = cur.next(Direction::Down) else {break};
+            let Some(next_cur) = cur.next(Direction::Down) else {
+                break;
+            };
             let overlaps = shape
                 .iter()
                 .map(|&x| add_point(next_cur, x))
@@ -242,19 +259,28 @@ lazy_static! {
     ];
 }
 
+const EXAMPLE_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("3068"), Some("1514285714288")],
+    params: &[],
+}];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "3068")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "3068")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "1514285714288")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "1514285714288")
     }
 }
@@ -0,0 +1,144 @@
+use crate::grid::{Grid3, Vector3};
+use crate::solutions::prelude::*;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<Vector3>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(points: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let grid = build_grid(points);
+
+        let ans: usize = points
+            .iter()
+            .map(|p| {
+                6 - p
+                    .iter_adjacent()
+                    .filter(|&p| grid.get(p) == Some(&Space::Magma))
+                    .count()
+            })
+            .sum();
+        Ok(ans.into())
+    }
+
+    fn problem2(points: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let mut grid = build_grid(points);
+        let mut frontier = vec![grid.min()];
+
+        while let Some(p) = frontier.pop() {
+            let Some(cell) = grid.get_mut(p) else {
+                continue;
+            };
+            if *cell != Space::Empty {
+                continue;
+            }
+            *cell = Space::Water;
+            frontier.extend(p.iter_adjacent());
+        }
+
+        let ans: usize = points
+            .iter()
+            .map(|p| {
+                p.iter_adjacent()
+                    .filter(|&p| grid.get(p) == Some(&Space::Water))
+                    .count()
+            })
+            .sum();
+
+        Ok(ans.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Space {
+    #[default]
+    Empty,
+    Magma,
+    Water,
+}
+
+// Builds a grid of the points with a layer of empty space all around them,
+// so water can flow around the outside of the droplet.
+fn build_grid(points: &[Vector3]) -> Grid3<Space> {
+    let margin = Vector3::new(1, 1, 1);
+    let (min, max) = Vector3::bounds(points.iter().copied()).unwrap_or_default();
+
+    let mut grid = Grid3::new(min - margin, max + margin, Space::Empty);
+    for &p in points {
+        grid[p] = Space::Magma;
+    }
+
+    grid
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Vector3>> {
+        let point = tuple((int, char(','), int, char(','), int))
+            .map(|(x, _, y, _, z)| Vector3::new(x, y, z));
+        ws_all_consuming(many1(ws_line(point)))(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "
+        2,2,2
+        1,2,2
+        3,2,2
+        2,1,2
+        2,3,2
+        2,2,1
+        2,2,3
+        2,2,4
+        2,2,6
+        1,2,5
+        3,2,5
+        2,1,5
+        2,3,5
+    ";
+
+pub const EXAMPLES: &[Example] = &[
+    Example {
+        name: "example",
+        input: EXAMPLE_INPUT,
+        answers: [Some("64"), Some("58")],
+        params: &[],
+    },
+    Example {
+        name: "small",
+        input: "1,1,1\n2,1,1",
+        answers: [Some("10"), Some("10")],
+        params: &[],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse("1,1,1\n2,1,1").unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "10");
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "64");
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "58")
+    }
+
+    #[test]
+    fn zero_and_negative_test() {
+        let data = Solver::parse("0,0,0\n-1,0,0").unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "10");
+        assert_eq!(Solver::problem2(&data).unwrap(), "10");
+    }
+}
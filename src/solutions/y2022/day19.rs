@@ -3,31 +3,57 @@ use std::collections::BinaryHeap;
 use arrayvec::ArrayVec;
 use rayon::prelude::*;
 
+use crate::cancel;
 use crate::solutions::prelude::*;
 use crate::utils::HeapElement;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let blueprints = parse!(input);
-    let ans: usize = blueprints
-        .par_iter()
-        .map(|b| b.id * simulate_blueprint(b, 24))
-        .sum();
+pub struct Solver;
 
-    Ok(ans.to_string())
-}
+const MINUTES1: Param<usize> = Param::new("minutes1", 24, "Minutes to collect geodes in problem 1");
+const MINUTES2: Param<usize> = Param::new("minutes2", 32, "Minutes to collect geodes in problem 2");
+const BLUEPRINTS2: Param<usize> = Param::new(
+    "blueprints2",
+    3,
+    "Blueprints the elephants left intact for problem 2",
+);
+
+impl Solution for Solver {
+    type Parsed = Vec<Blueprint>;
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&MINUTES1, &MINUTES2, &BLUEPRINTS2];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(blueprints: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let minutes = MINUTES1.get()?;
+        let cancel = cancel::current();
+        let ans = blueprints
+            .par_iter()
+            .map(|b| Ok(b.id * simulate_blueprint(b, minutes, &cancel)?))
+            .sum::<Result<usize, anyhow::Error>>()?;
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let mut blueprints = parse!(input);
-    blueprints.truncate(3);
-    let ans: usize = blueprints
-        .par_iter()
-        .map(|b| simulate_blueprint(b, 32))
-        .product();
+        Ok(ans.into())
+    }
 
-    Ok(ans.to_string())
+    fn problem2(blueprints: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let blueprints = &blueprints[..blueprints.len().min(BLUEPRINTS2.get()?)];
+        let minutes = MINUTES2.get()?;
+        let cancel = cancel::current();
+        let ans = blueprints
+            .par_iter()
+            .map(|b| simulate_blueprint(b, minutes, &cancel))
+            .product::<Result<usize, anyhow::Error>>()?;
+
+        Ok(ans.into())
+    }
 }
 
-fn simulate_blueprint(blueprint: &Blueprint, time: usize) -> usize {
+fn simulate_blueprint(
+    blueprint: &Blueprint,
+    time: usize,
+    cancel: &cancel::Token,
+) -> Result<usize, anyhow::Error> {
     let initial_state = State {
         time_remaining: time,
         resources: ResourceState::default(),
@@ -41,13 +67,15 @@ fn simulate_blueprint(blueprint: &Blueprint, time: usize) -> usize {
 
     let mut frontier = BinaryHeap::new();
     frontier.push(HeapElement::from((
-        initial_state.high_mark(&blueprint),
+        initial_state.high_mark(blueprint),
         initial_state,
     )));
 
     while let Some(state) = frontier.pop().map(|x| x.value) {
+        cancel.check()?;
+
         if state.time_remaining == 0 {
-            return state.resources.geode;
+            return Ok(state.resources.geode);
         }
 
         let mut robot_costs = ArrayVec::from(blueprint.robot_costs.clone());
@@ -69,7 +97,7 @@ fn simulate_blueprint(blueprint: &Blueprint, time: usize) -> usize {
                         robots: state.robots.add_resource(costs.robot_type, 1),
                     };
                     frontier.push(HeapElement::from((
-                        new_state.high_mark(&blueprint),
+                        new_state.high_mark(blueprint),
                         new_state,
                     )));
                 } else {
@@ -86,7 +114,7 @@ fn simulate_blueprint(blueprint: &Blueprint, time: usize) -> usize {
             resources,
         };
         frontier.push(HeapElement::from((
-            new_state.high_mark(&blueprint),
+            new_state.high_mark(blueprint),
             new_state,
         )));
     }
@@ -224,7 +252,7 @@ mod parser {
         }
 
         let blueprint = tuple((
-            delimited(mtag!("Blueprint "), uint, mtag!(":")),
+            terminated(uint, mtag!(":")),
             delimited(mtag!("Each ore robot costs"), uint, mtag!("ore.")),
             delimited(mtag!("Each clay robot costs"), uint, mtag!("ore.")),
             delimited(
@@ -280,15 +308,12 @@ mod parser {
             }
         });
 
+        let blueprint = context("blueprint", preceded(mtag!("Blueprint "), cut(blueprint)));
         ws_all_consuming(many1(blueprint))(input)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         Blueprint 1:
             Each ore robot costs 4 ore.
             Each clay robot costs 2 ore.
@@ -302,13 +327,35 @@ mod tests {
             Each geode robot costs 3 ore and 12 obsidian.
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("33"), Some("3472")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "33")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "33")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "3472")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "3472")
+    }
+
+    #[test]
+    fn cancel_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        let token = cancel::Token::default();
+        token.cancel();
+
+        assert!(cancel::with_token(token, || Solver::problem1(&data)).is_err());
     }
 }
@@ -1,22 +1,30 @@
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let numbers = parse!(input);
-    let mut list = List::new(numbers);
-    list.mix();
+pub struct Solver;
 
-    Ok(list.coordinate()?.to_string())
-}
+impl Solution for Solver {
+    type Parsed = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    const DECRIPTION_KEY: isize = 811589153;
-    let numbers = parse!(input);
-    let mut list = List::new(numbers.iter().copied().map(|x| x * DECRIPTION_KEY));
-    for _ in 0..10 {
+    fn problem1(numbers: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let mut list = List::new(numbers.iter().copied());
         list.mix();
+
+        Ok(list.coordinate()?.into())
     }
 
-    Ok(list.coordinate()?.to_string())
+    fn problem2(numbers: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        const DECRIPTION_KEY: isize = 811589153;
+        let mut list = List::new(numbers.iter().copied().map(|x| x * DECRIPTION_KEY));
+        for _ in 0..10 {
+            list.mix();
+        }
+
+        Ok(list.coordinate()?.into())
+    }
 }
 
 struct List {
@@ -97,11 +105,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         1
         2
         -3
@@ -111,13 +115,26 @@ mod tests {
         4
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("3"), Some("1623178306")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "3")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "3")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "1623178306")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "1623178306")
     }
 }
@@ -4,35 +4,48 @@ use arrayvec::ArrayString;
 
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let vars = parse!(input);
-    let root = vars.get("root").ok_or(anyhow!("root not found"))?;
+pub struct Solver;
 
-    let ans = root.expand(&vars).simplify();
-    let Expr::Value(ans) = ans else {bail!("expr did not fully simplify")};
+impl Solution for Solver {
+    type Parsed = HashMap<Ident, Expr>;
 
-    Ok(ans.to_string())
-}
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(vars: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let root = vars.get("root").ok_or(anyhow!("root not found"))?;
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let mut vars = parse!(input);
-    vars.remove("humn");
+        let ans = root.expand(vars).simplify();
+        let Expr::Value(ans) = ans else {
+            bail!("expr did not fully simplify")
+        };
 
-    let root = vars.get("root").ok_or(anyhow!("root not found"))?;
-    let Expr::Operation(root_op) = root else {bail!("root is not an operation")};
+        Ok(ans.into())
+    }
 
-    let a = root_op.a.expand(&vars).simplify();
-    let b = root_op.b.expand(&vars).simplify();
+    fn problem2(vars: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let mut vars = vars.clone();
+        vars.remove("humn");
 
-    let (rhs, lhs) = match (a, b) {
-        (rhs, Expr::Value(lhs)) => (rhs, lhs),
-        (Expr::Value(lhs), rhs) => (rhs, lhs),
-        _ => bail!("lhs or rhs must be a value"),
-    };
+        let root = vars.get("root").ok_or(anyhow!("root not found"))?;
+        let Expr::Operation(root_op) = root else {
+            bail!("root is not an operation")
+        };
 
-    let ans = isolate_var(&rhs, lhs)?;
+        let a = root_op.a.expand(&vars).simplify();
+        let b = root_op.b.expand(&vars).simplify();
 
-    Ok(ans.to_string())
+        let (rhs, lhs) = match (a, b) {
+            (rhs, Expr::Value(lhs)) => (rhs, lhs),
+            (Expr::Value(lhs), rhs) => (rhs, lhs),
+            _ => bail!("lhs or rhs must be a value"),
+        };
+
+        let ans = isolate_var(&rhs, lhs)?;
+
+        Ok(ans.into())
+    }
 }
 
 fn isolate_var(mut rhs: &Expr, mut lhs: i64) -> anyhow::Result<i64> {
@@ -96,7 +109,9 @@ impl Expr {
             }
             Self::Value(_) => self.clone(),
             Self::Var(var) => {
-                let Some(e) = vars.get(var) else {return self.clone()};
+                let Some(e) = vars.get(var) else {
+                    return self.clone();
+                };
                 e.expand(vars)
             }
         }
@@ -123,11 +138,7 @@ impl Expr {
     }
 
     fn is_value(&self) -> bool {
-        if let Self::Value(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Value(_))
     }
 }
 
@@ -207,10 +218,7 @@ mod parser {
                 b: Expr::Var(b).into(),
             });
 
-        let expr = alt((
-            uint.map(|x| Expr::Value(x)),
-            operation.map(|x| Expr::Operation(x)),
-        ));
+        let expr = alt((uint.map(Expr::Value), operation.map(Expr::Operation)));
 
         separated_pair(ident, tag(": "), expr).parse(input)
     }
@@ -220,11 +228,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "root: pppw + sjmn
+const EXAMPLE_INPUT: &str = "root: pppw + sjmn
     dbpl: 5
     cczh: sllz + lgvd
     zczc: 2
@@ -240,13 +244,26 @@ mod tests {
     drzm: hmdt - zczc
     hmdt: 32";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("152"), Some("301")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "152")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "152")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "301")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "301")
     }
 }
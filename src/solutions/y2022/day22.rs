@@ -3,71 +3,92 @@ use std::collections::HashMap;
 use crate::grid::{Direction, Point};
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let (map, instructions) = parse!(input);
-    let mut cur = map.starting_location();
-    let mut dir = Direction::Right;
-
-    let mut m = HashMap::new();
-    m.insert(cur, dir);
-
-    for inst in instructions {
-        match inst {
-            Instruction::Turn(t) => dir = t.apply(dir),
-            Instruction::Step(n) => {
-                for _ in 0..n {
-                    let next = map.step(&cur, dir);
-                    if map.get(&next).unwrap() == Cell::Wall {
-                        break;
+pub struct Solver;
+
+const CUBE_SIZE: Param<usize> = Param::new(
+    "cube_size",
+    50,
+    "Side length of the cube, 50 for real inputs and 4 for the example",
+);
+
+impl Solution for Solver {
+    type Parsed = (Map, Vec<Instruction>);
+    const PARAMS: &'static [&'static dyn AnyParam] = &[&CUBE_SIZE];
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (map, instructions) = data;
+        let mut cur = map.starting_location();
+        let mut dir = Direction::Right;
+
+        let mut m = HashMap::new();
+        m.insert(cur, dir);
+
+        for &inst in instructions {
+            match inst {
+                Instruction::Turn(t) => dir = t.apply(dir),
+                Instruction::Step(n) => {
+                    for _ in 0..n {
+                        let next = map.step(&cur, dir);
+                        if map.get(&next).unwrap() == Cell::Wall {
+                            break;
+                        }
+                        cur = next;
+                        m.insert(cur, dir);
                     }
-                    cur = next;
-                    m.insert(cur, dir);
                 }
             }
         }
-    }
 
-    let ans = 1000 * (cur.y + 1) + 4 * (cur.x + 1) + direction_value(dir);
-    Ok(ans.to_string())
-}
+        let ans = 1000 * (cur.y + 1) + 4 * (cur.x + 1) + direction_value(dir);
+        Ok(ans.into())
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    problem2_(input, false)
-}
+    fn problem2(data: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (map, instructions) = data;
 
-fn problem2_(input: &str, is_test: bool) -> Result<String, anyhow::Error> {
-    let (map, instructions) = parse!(input);
+        // The example folds into a cube differently from the real inputs.
+        let cube_length = CUBE_SIZE.get()?;
+        let sides = match cube_length {
+            50 => SIDES,
+            4 => SIDES_TEST,
+            _ => bail!("no cube net known for side length {}", cube_length),
+        };
 
-    let mut walker = CubeWalker {
-        sides: if is_test { SIDES_TEST } else { SIDES },
-        cube_length: if is_test { 4 } else { 50 },
-        direction: Direction::Right,
-        side: 1,
-        point: Point::new(0, 0),
-    };
+        let mut walker = CubeWalker {
+            sides,
+            cube_length,
+            direction: Direction::Right,
+            side: 1,
+            point: Point::new(0, 0),
+        };
 
-    let mut m = HashMap::new();
-    m.insert(walker.as_point(), walker.direction);
-
-    for inst in instructions {
-        match inst {
-            Instruction::Turn(t) => walker = walker.turn(t),
-            Instruction::Step(n) => {
-                for _ in 0..n {
-                    let next = walker.step();
-                    if map.get(&next.as_point()).unwrap() == Cell::Wall {
-                        break;
+        let mut m = HashMap::new();
+        m.insert(walker.as_point(), walker.direction);
+
+        for &inst in instructions {
+            match inst {
+                Instruction::Turn(t) => walker = walker.turn(t),
+                Instruction::Step(n) => {
+                    for _ in 0..n {
+                        let next = walker.step();
+                        if map.get(&next.as_point()).unwrap() == Cell::Wall {
+                            break;
+                        }
+                        walker = next;
+                        m.insert(walker.as_point(), walker.direction);
                     }
-                    walker = next;
-                    m.insert(walker.as_point(), walker.direction);
                 }
             }
         }
-    }
 
-    let cur = walker.as_point();
-    let ans = 1000 * (cur.y + 1) + 4 * (cur.x + 1) + direction_value(walker.direction);
-    Ok(ans.to_string())
+        let cur = walker.as_point();
+        let ans = 1000 * (cur.y + 1) + 4 * (cur.x + 1) + direction_value(walker.direction);
+        Ok(ans.into())
+    }
 }
 
 fn direction_value(d: Direction) -> usize {
@@ -455,8 +476,8 @@ mod parser {
             value(TurnDirection::Right, char('R')),
         ));
         let instruction = alt((
-            uint.map(|x| Instruction::Step(x)),
-            turn_direction.map(|x| Instruction::Turn(x)),
+            uint.map(Instruction::Step),
+            turn_direction.map(Instruction::Turn),
         ));
         let instructions = many1(instruction);
 
@@ -466,11 +487,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "        ...#
+const EXAMPLE_INPUT: &str = "        ...#
         .#..
         #...
         ....
@@ -485,13 +502,29 @@ mod tests {
 
 10R5L5R10L4R5L5";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("6032"), Some("5031")],
+    params: &[("cube_size", "4")],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::{self, Values};
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "6032")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "6032")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2_(EXAMPLE_INPUT, true).unwrap(), "5031")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        let params = Values::from(EXAMPLES[0].params);
+        let ans = params::with_values(params, || Solver::problem2(&data));
+        assert_eq!(ans.unwrap(), "5031")
     }
 }
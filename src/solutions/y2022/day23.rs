@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+
+use ahash::HashMap;
+
+use crate::grid::{Direction, ExpandingGrid, Vector};
+use crate::solutions::prelude::*;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<Vector>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(elves: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (_, mut grid) = simulate(elves, 10);
+        grid.trim();
+        let ans = grid.area() - elves.len();
+
+        Ok(ans.into())
+    }
+
+    fn problem2(elves: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let (ans, _) = simulate(elves, usize::MAX);
+        Ok(ans.into())
+    }
+}
+
+fn simulate(elves: &[Vector], max_iterations: usize) -> (usize, ExpandingGrid<bool>) {
+    let mut grid = ExpandingGrid::new(false);
+    for &p in elves {
+        grid.set(p, true);
+    }
+    grid.trim();
+
+    // North, south, west then east. The first row of the input is y = 0, so
+    // north is towards smaller y.
+    let mut directions = VecDeque::from(vec![
+        Direction::Down,
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+    ]);
+
+    // Proposal of Value -> Key. If value is None, Key is blocked.
+    let mut proposals: HashMap<Vector, Option<Vector>> = HashMap::default();
+
+    for i in 0..max_iterations {
+        proposals.drain();
+        let elves = grid
+            .iter()
+            .filter_map(|(p, v)| if *v { Some(p) } else { None });
+        for elf in elves {
+            if elf.iter_surrounding().all(|p| !grid.get(p)) {
+                continue;
+            }
+
+            let Some(d) = directions
+                .iter()
+                .find(|&&d| !elf.iter_facing(d).any(|p| *grid.get(p)))
+                .copied()
+            else {
+                continue;
+            };
+
+            proposals
+                .entry(elf.step(d))
+                .and_modify(|e| *e = None)
+                .or_insert(Some(elf));
+        }
+
+        if proposals.values().all(|x| x.is_none()) {
+            return (i + 1, grid);
+        }
+
+        for (to, from) in proposals.iter() {
+            if let Some(from) = from {
+                grid.set(*from, false);
+                grid.set(*to, true);
+            }
+        }
+
+        directions.rotate_left(1);
+    }
+
+    (max_iterations, grid)
+}
+
+mod parser {
+    use super::*;
+    use crate::parser::prelude::*;
+
+    pub fn parse(input: &str) -> IResult<&str, Vec<Vector>> {
+        let cell = alt((value(false, char('.')), value(true, char('#'))));
+        let row = ws_line(many1(cell));
+        let graph = many1(row).map(|graph| {
+            graph
+                .into_iter()
+                .enumerate()
+                .flat_map(|(i, row)| {
+                    row.into_iter()
+                        .enumerate()
+                        .filter(|(_, is_elf)| *is_elf)
+                        .map(move |(j, _)| Vector::new(j as isize, i as isize))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        ws_all_consuming(graph)(input)
+    }
+}
+
+const EXAMPLE_INPUT: &str = "
+        ..............
+        ..............
+        .......#......
+        .....###.#....
+        ...#...#.#....
+        ....#...##....
+        ...#.###......
+        ...##.#.##....
+        ....#..#......
+        ..............
+        ..............
+        ..............
+    ";
+
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("110"), Some("20")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem1_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "110")
+    }
+
+    #[test]
+    fn problem2_test() {
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "20")
+    }
+}
@@ -8,24 +8,32 @@ use bitflags::bitflags;
 use crate::grid::{Direction, Grid, Point};
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let initial_state = parse!(input);
-    let valley = BlizzardValley::new(initial_state);
+pub struct Solver;
 
-    let ans = shortest_path(&valley, valley.start, valley.end, 0)?;
+impl Solution for Solver {
+    type Parsed = Grid<Cell>;
 
-    Ok(ans.to_string())
-}
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(initial_state: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let valley = BlizzardValley::new(initial_state.clone());
+
+        let ans = shortest_path(&valley, valley.start, valley.end, 0)?;
+
+        Ok(ans.into())
+    }
 
-pub fn problem2(input: &str) -> Result<String, anyhow::Error> {
-    let initial_state = parse!(input);
-    let valley = BlizzardValley::new(initial_state);
+    fn problem2(initial_state: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let valley = BlizzardValley::new(initial_state.clone());
 
-    let trip1 = shortest_path(&valley, valley.start, valley.end, 0)?;
-    let trip2 = shortest_path(&valley, valley.end, valley.start, trip1)?;
-    let trip3 = shortest_path(&valley, valley.start, valley.end, trip2)?;
+        let trip1 = shortest_path(&valley, valley.start, valley.end, 0)?;
+        let trip2 = shortest_path(&valley, valley.end, valley.start, trip1)?;
+        let trip3 = shortest_path(&valley, valley.start, valley.end, trip2)?;
 
-    Ok(trip3.to_string())
+        Ok(trip3.into())
+    }
 }
 
 fn shortest_path(
@@ -54,13 +62,13 @@ fn shortest_path(
         for p in next_points {
             if p == end {
                 return Ok(minute);
-            } else if grid.get(p).map(|c| c.is_empty()).unwrap_or(false) {
-                if seen.insert((p, minute % valley.repeat_interval())) {
-                    frontier.push((
-                        Reverse(end.manhattan_distance(&p) + minute),
-                        ExpeditionState { point: p, minute },
-                    ));
-                }
+            } else if grid.get(p).map(|c| c.is_empty()).unwrap_or(false)
+                && seen.insert((p, minute % valley.repeat_interval()))
+            {
+                frontier.push((
+                    Reverse(end.manhattan_distance(&p) + minute),
+                    ExpeditionState { point: p, minute },
+                ));
             }
         }
     }
@@ -82,24 +90,22 @@ pub struct BlizzardValley {
 
 impl BlizzardValley {
     fn new(grid: Grid<Cell>) -> Self {
+        let end_y = grid.height() - 1;
+
         let start_x = grid
-            .cells
-            .first()
+            .row(0)
             .unwrap()
             .iter()
             .position(|x| !x.contains(Cell::WALL))
             .unwrap();
 
         let end_x = grid
-            .cells
-            .last()
+            .row(end_y)
             .unwrap()
             .iter()
             .position(|x| !x.contains(Cell::WALL))
             .unwrap();
 
-        let end_y = grid.cells.len() - 1;
-
         let mut ret = BlizzardValley {
             blizzard_locations: vec![grid],
             start: Point::new(start_x, 0),
@@ -129,12 +135,7 @@ impl BlizzardValley {
     }
 
     fn step_blizzard(old: &Grid<Cell>) -> Grid<Cell> {
-        let mut new = Grid::new(
-            old.cells
-                .iter()
-                .map(|r| vec![Cell::empty(); r.len()])
-                .collect(),
-        );
+        let mut new = Grid::new(old.width(), old.height(), Cell::empty());
 
         for p in old.iter_points() {
             let cur = old.get(p).unwrap();
@@ -149,10 +150,10 @@ impl BlizzardValley {
                     let new_p = match next {
                         Some(x) => x,
                         None => match d {
-                            Direction::Left => Point::new(old.cells[p.y].len() - 2, p.y),
+                            Direction::Left => Point::new(old.width() - 2, p.y),
                             Direction::Right => Point::new(1, p.y),
                             Direction::Up => Point::new(p.x, 1),
-                            Direction::Down => Point::new(p.x, old.cells.len() - 2),
+                            Direction::Down => Point::new(p.x, old.height() - 2),
                         },
                     };
 
@@ -222,12 +223,13 @@ impl std::fmt::Display for Grid<Cell> {
             Ok(())
         }
 
-        let Some((first, rest)) = self.cells.split_first() else {
-            return f.write_str("<EMPTY GRID>")
+        let mut rows = self.rows();
+        let Some(first) = rows.next() else {
+            return f.write_str("<EMPTY GRID>");
         };
 
         render_row(f, first)?;
-        for row in rest {
+        for row in rows {
             f.write_char('\n')?;
             render_row(f, row)?;
         }
@@ -251,17 +253,13 @@ mod parser {
         ));
 
         let row = many1(cell);
-        let grid = many1(ws_line(row)).map(|x| Grid::new(x));
+        let grid = map_res(many1(ws_line(row)), Grid::from_rows);
 
         ws_all_consuming(grid)(input)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         #.######
         #>>.<^<#
         #.<..<<#
@@ -270,13 +268,26 @@ mod tests {
         ######.#
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("18"), Some("54")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "18")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "18")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "54")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "54")
     }
 }
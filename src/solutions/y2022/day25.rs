@@ -1,14 +1,34 @@
+use crate::parser::parse_lines;
 use crate::solutions::prelude::*;
 
-pub fn problem1(input: &str) -> Result<String, anyhow::Error> {
-    let nums = parse!(input);
-    let sum: i64 = nums.iter().sum();
-    let n = base_5(sum);
-    Ok(base5_to_snafu(&n))
-}
+pub struct Solver;
+
+impl Solution for Solver {
+    type Parsed = Vec<i64>;
+    const STREAMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Parsed, anyhow::Error> {
+        Ok(parse!(input))
+    }
+
+    fn problem1(nums: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        let sum: i64 = nums.iter().sum();
+        let n = base_5(sum);
+        Ok(base5_to_snafu(&n).into())
+    }
+
+    fn problem2(_nums: &Self::Parsed) -> Result<Answer, anyhow::Error> {
+        Ok("[Start The Blender]".into())
+    }
 
-pub fn problem2(_input: &str) -> Result<String, anyhow::Error> {
-    Ok("[Start The Blender]".to_owned())
+    fn stream(problem: usize, input: &mut dyn BufRead) -> Result<Answer, anyhow::Error> {
+        if problem == 2 {
+            return Ok("[Start The Blender]".into());
+        }
+
+        let sum = parse_lines(input, parser::snafu_num).sum::<Result<i64, _>>()?;
+        Ok(base5_to_snafu(&base_5(sum)).into())
+    }
 }
 
 // BCD... but base 5
@@ -66,7 +86,7 @@ mod parser {
         ws_all_consuming(many1(ws_line(snafu_num)))(input)
     }
 
-    fn snafu_num(input: &str) -> IResult<&str, i64> {
+    pub fn snafu_num(input: &str) -> IResult<&str, i64> {
         let digit = alt((
             value(2, char('2')),
             value(1, char('1')),
@@ -81,11 +101,7 @@ mod parser {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = "
+const EXAMPLE_INPUT: &str = "
         1=-0-2
         12111
         2=0=
@@ -101,13 +117,26 @@ mod tests {
         122
     ";
 
+pub const EXAMPLES: &[Example] = &[Example {
+    name: "example",
+    input: EXAMPLE_INPUT,
+    answers: [Some("2=-1=0"), Some("[Start The Blender]")],
+    params: &[],
+}];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn problem1_test() {
-        assert_eq!(problem1(EXAMPLE_INPUT).unwrap(), "2=-1=0")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem1(&data).unwrap(), "2=-1=0")
     }
 
     #[test]
     fn problem2_test() {
-        assert_eq!(problem2(EXAMPLE_INPUT).unwrap(), "[Start The Blender]")
+        let data = Solver::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(Solver::problem2(&data).unwrap(), "[Start The Blender]")
     }
 }
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::report::Record;
+
+/// Where `run-all` keeps its timings unless told otherwise: `timings.txt` in
+/// `CARGO_TARGET_DIR`, or in `target` under the current directory.
+pub fn default_path() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("timings.txt")
+}
+
+/// Solve times of the last successful run of each problem keyed by (year,
+/// day, problem).
+///
+/// Stored as one line per problem: `YYYY-DD-P solve_ns`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    /// The last solve time of each problem.
+    pub results: HashMap<(usize, usize, usize), Duration>,
+}
+
+impl Timings {
+    /// Reads a timings file. A missing file has no timings.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(data) => Self::parse(&data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).context("failed to read timings file"),
+        }
+    }
+
+    /// Writes the timings to a file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("failed to create timings directory")?;
+        }
+        std::fs::write(path, self.to_string()).context("failed to write timings file")
+    }
+
+    /// Parses the contents of a timings file.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut results = HashMap::new();
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, solve) =
+                parse_line(line).ok_or_else(|| anyhow!("line {}: malformed timing", i + 1))?;
+            results.insert(key, solve);
+        }
+
+        Ok(Timings { results })
+    }
+
+    /// Replaces the timings of every successful record.
+    pub fn update(&mut self, records: &[Record]) {
+        for r in records {
+            if let (true, Some(solve)) = (r.success(), r.solve) {
+                self.results.insert((r.year, r.day, r.problem), solve);
+            }
+        }
+    }
+
+    /// Returns the last solve time of a problem.
+    pub fn get(&self, year: usize, day: usize, problem: usize) -> Option<Duration> {
+        self.results.get(&(year, day, problem)).copied()
+    }
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut keys: Vec<_> = self.results.keys().collect();
+        keys.sort();
+
+        for &(year, day, problem) in keys {
+            let solve = self.results[&(year, day, problem)];
+            writeln!(f, "{}-{:02}-{} {}", year, day, problem, solve.as_nanos())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<((usize, usize, usize), Duration)> {
+    let (key, solve) = line.split_once(' ')?;
+    let mut key = key.split('-').map(|x| x.parse().ok());
+    let (year, day, problem) = (key.next()??, key.next()??, key.next()??);
+    if key.next().is_some() {
+        return None;
+    }
+
+    let solve = Duration::from_nanos(solve.trim().parse().ok()?);
+    Some(((year, day, problem), solve))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let mut timings = Timings::default();
+        timings.update(&[
+            Record {
+                year: 2022,
+                day: 15,
+                problem: 2,
+                solve: Some(Duration::from_millis(700)),
+                ..Default::default()
+            },
+            Record {
+                year: 2022,
+                day: 16,
+                problem: 1,
+                error: Some("failed".to_owned()),
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(timings.to_string(), "2022-15-2 700000000\n");
+        assert_eq!(Timings::parse(&timings.to_string()).unwrap(), timings);
+        assert!(Timings::parse("2022-15 7").is_err());
+    }
+}
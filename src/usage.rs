@@ -0,0 +1,333 @@
+#[cfg(feature = "alloc-stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+/// A global allocator that counts allocations and tracks the peak heap size.
+///
+/// Installed by the `alloc-stats` feature. The counters are process wide, so
+/// they are only reported for problems solved while no others were, see
+/// [`Sampler`].
+#[cfg(feature = "alloc-stats")]
+pub struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+// Samplers currently measuring and the number ever started, to tell whether
+// any two overlapped.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+static STARTED: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "alloc-stats")]
+impl CountingAllocator {
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Resources used while solving a problem.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Most bytes allocated at once above what was allocated at the start.
+    /// Only known with the counting allocator and when no other problem was
+    /// solved at the same time.
+    pub peak_heap: Option<usize>,
+    /// Number of allocations and reallocations. Only known with the counting
+    /// allocator and when no other problem was solved at the same time.
+    pub allocations: Option<u64>,
+    /// CPU time spent in user mode. Only known when no other problem was
+    /// solved at the same time, since it is measured for the whole process.
+    pub user: Option<Duration>,
+    /// CPU time spent in the kernel. Only known when no other problem was
+    /// solved at the same time, since it is measured for the whole process.
+    pub system: Option<Duration>,
+    /// High water mark of the resident set of the whole process in bytes.
+    pub max_rss: u64,
+}
+
+/// Takes a snapshot of the counters so [`Sampler::finish`] can tell how much
+/// was used in between.
+///
+/// The counters are process wide. When samplers overlap, e.g. in `run-all
+/// --parallel`, the CPU and heap numbers of each would include the others',
+/// so they are left out of the [`Usage`] of every sampler involved.
+pub struct Sampler {
+    heap: usize,
+    allocations: u64,
+    rusage: Rusage,
+    // Whether another sampler was running at the start.
+    shared: bool,
+    // The value of `STARTED` after this sampler started.
+    started: u64,
+}
+
+impl Sampler {
+    /// Starts measuring.
+    pub fn start() -> Self {
+        let shared = RUNNING.fetch_add(1, Ordering::SeqCst) > 0;
+        let started = STARTED.fetch_add(1, Ordering::SeqCst) + 1;
+        let heap = CURRENT.load(Ordering::Relaxed);
+        if !shared {
+            // Only the peak from here on is of interest.
+            PEAK.store(heap, Ordering::Relaxed);
+        }
+
+        Sampler {
+            heap,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            rusage: Rusage::get(Who::Process),
+            shared,
+            started,
+        }
+    }
+
+    /// Returns what was used since [`Sampler::start`].
+    pub fn finish(self) -> Usage {
+        let rusage = Rusage::get(Who::Process);
+        let peak = PEAK.load(Ordering::Relaxed);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let alone = !self.shared && STARTED.load(Ordering::SeqCst) == self.started;
+        let installed = alone && cfg!(feature = "alloc-stats");
+
+        Usage {
+            peak_heap: installed.then(|| peak.saturating_sub(self.heap)),
+            allocations: installed.then(|| allocations - self.allocations),
+            user: alone.then(|| rusage.user.saturating_sub(self.rusage.user)),
+            system: alone.then(|| rusage.system.saturating_sub(self.rusage.system)),
+            max_rss: rusage.max_rss,
+        }
+    }
+}
+
+impl Drop for Sampler {
+    /// Stops counting as running, also when the problem failed and
+    /// [`Sampler::finish`] was never called.
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns the user and system CPU time used so far by the process and its
+/// children that have been waited for.
+pub fn cpu_time() -> Duration {
+    let own = Rusage::get(Who::Process);
+    let children = Rusage::get(Who::Children);
+    own.user + own.system + children.user + children.system
+}
+
+enum Who {
+    Process,
+    Children,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Rusage {
+    user: Duration,
+    system: Duration,
+    max_rss: u64,
+}
+
+impl Rusage {
+    #[cfg(unix)]
+    fn get(who: Who) -> Self {
+        let who = match who {
+            Who::Process => libc::RUSAGE_SELF,
+            Who::Children => libc::RUSAGE_CHILDREN,
+        };
+
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+        // SAFETY: getrusage only writes to the struct we pass it.
+        let usage = unsafe {
+            if libc::getrusage(who, usage.as_mut_ptr()) != 0 {
+                return Self::default();
+            }
+            usage.assume_init()
+        };
+
+        let time = |t: libc::timeval| {
+            Duration::from_secs(t.tv_sec as u64) + Duration::from_micros(t.tv_usec as u64)
+        };
+
+        // Linux reports the RSS in kilobytes, macOS in bytes.
+        let rss_unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+
+        Rusage {
+            user: time(usage.ru_utime),
+            system: time(usage.ru_stime),
+            max_rss: usage.ru_maxrss as u64 * rss_unit,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn get(_who: Who) -> Self {
+        Self::default()
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{}B", bytes),
+        _ => format!("{:.1}{}", value, UNITS[unit]),
+    }
+}
+
+/// Describes the usage on one line for text output.
+pub fn summary(usage: &Usage) -> String {
+    let mut ret = match (usage.user, usage.system) {
+        (Some(user), Some(system)) => format!("user {:?}, sys {:?}, ", user, system),
+        _ => String::new(),
+    };
+    ret += &format!("max rss {}", format_bytes(usage.max_rss));
+    if let (Some(heap), Some(allocations)) = (usage.peak_heap, usage.allocations) {
+        ret += &format!(
+            ", peak heap {} in {} allocs",
+            format_bytes(heap as u64),
+            allocations
+        );
+    }
+
+    ret
+}
+
+impl fmt::Display for Usage {
+    /// Writes `user_ns system_ns max_rss peak_heap allocations` with `-` for
+    /// unknown values. Parsed back by `FromStr`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opt = |x: Option<u64>| x.map_or("-".to_owned(), |x| x.to_string());
+        let nanos = |x: Option<Duration>| x.map_or("-".to_owned(), |x| x.as_nanos().to_string());
+        write!(
+            f,
+            "{} {} {} {} {}",
+            nanos(self.user),
+            nanos(self.system),
+            self.max_rss,
+            opt(self.peak_heap.map(|x| x as u64)),
+            opt(self.allocations)
+        )
+    }
+}
+
+impl std::str::FromStr for Usage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let fields: Vec<&str> = s.split(' ').collect();
+        let [user, system, max_rss, peak_heap, allocations] = fields[..] else {
+            return Err(anyhow!("malformed usage: {:?}", s));
+        };
+
+        let opt = |x: &str| -> Result<Option<u64>> {
+            match x {
+                "-" => Ok(None),
+                x => Ok(Some(x.parse()?)),
+            }
+        };
+
+        Ok(Usage {
+            peak_heap: opt(peak_heap)?.map(|x| x as usize),
+            allocations: opt(allocations)?,
+            user: opt(user)?.map(Duration::from_nanos),
+            system: opt(system)?.map(Duration::from_nanos),
+            max_rss: max_rss.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let usage = Usage {
+            peak_heap: Some(4096),
+            allocations: None,
+            user: Some(Duration::from_micros(1500)),
+            system: None,
+            max_rss: 1 << 20,
+        };
+
+        assert_eq!(usage.to_string().parse::<Usage>().unwrap(), usage);
+        assert!("1 2 3".parse::<Usage>().is_err());
+    }
+
+    #[test]
+    fn overlap_test() {
+        // Other tests may be sampling too, which only adds overlap.
+        let a = Sampler::start();
+        let b = Sampler::start();
+        let b = b.finish();
+        let a = a.finish();
+        assert_eq!((a.user, a.peak_heap, a.allocations), (None, None, None));
+        assert_eq!((b.user, b.peak_heap, b.allocations), (None, None, None));
+    }
+
+    #[test]
+    fn drop_test() {
+        drop(Sampler::start());
+
+        // Other tests may still be sampling, but once they are done a new
+        // sampler has to be alone again.
+        let deadline = std::time::Instant::now() + Duration::from_secs(60);
+        loop {
+            let usage = Sampler::start().finish();
+            if usage.user.is_some() {
+                assert!(usage.system.is_some());
+                assert_eq!(usage.peak_heap.is_some(), cfg!(feature = "alloc-stats"));
+                break;
+            }
+            assert!(std::time::Instant::now() < deadline, "sampler never alone");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn format_bytes_test() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 << 30), "3.0GiB");
+    }
+}
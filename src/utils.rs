@@ -1,6 +1,7 @@
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
+/// Returns the `n` largest items in no particular order.
 pub fn top_n<I, T>(iter: I, n: usize) -> impl std::iter::Iterator<Item = T>
 where
     I: std::iter::IntoIterator<Item = T>,
@@ -11,20 +12,23 @@ where
         if heap.len() < n {
             heap.push(Reverse(v));
         } else {
-            heap.peek_mut().map(|mut x| {
+            if let Some(mut x) = heap.peek_mut() {
                 if v > x.0 {
                     *x = Reverse(v)
                 }
-            });
+            }
         }
     }
 
-    heap.into_iter().map(|x| x.0).into_iter()
+    heap.into_iter().map(|x| x.0)
 }
 
+/// A value in a [`std::collections::BinaryHeap`] ordered only by its key.
 #[derive(Debug)]
 pub struct HeapElement<K, V> {
+    /// The key the element is ordered by.
     pub key: K,
+    /// The value carried along with the key.
     pub value: V,
 }
 